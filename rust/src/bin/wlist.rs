@@ -0,0 +1,326 @@
+//! `wlist`, a command-line tool for sampling and inspecting weighted tables.
+//!
+//! Build with the `cli` feature enabled. Run `wlist --help` for usage.
+
+use std::error::{ Error };
+use std::fs::{ File };
+use std::io::{ self, Read, Write };
+use std::path::{ Path, PathBuf };
+use std::process::{ ExitCode };
+
+use clap::{ Args, Parser, Subcommand, ValueEnum };
+use rand::prelude::*;
+use rand::rngs::{ StdRng };
+
+use weighted_list::*;
+
+
+type Table = WeightedList<String, f64>;
+
+
+#[derive(Parser)]
+#[command(name = "wlist", version, about = "Sample and inspect weighted tables")]
+struct Cli
+{
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command
+{
+    /// Select random values from a table
+    Sample {
+        #[command(flatten)]
+        input: Input,
+
+        /// How many values to select
+        #[arg(short = 'n', long, default_value_t = 1)]
+        count: usize,
+
+        /// Seed for the random number generator, for reproducible output
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Select unique values, so at most one of each value is selected
+        #[arg(long, conflicts_with = "no_replace")]
+        unique: bool,
+
+        /// Decrement the weight of each selected item by 1
+        #[arg(long)]
+        no_replace: bool,
+    },
+
+    /// Show the total weight, probabilities and entropy of a table
+    Stats {
+        #[command(flatten)]
+        input: Input,
+    },
+
+    /// Normalise the weights of a table to sum to 1
+    #[command(visible_alias = "normalize")]
+    Normalise {
+        #[command(flatten)]
+        input: Input,
+
+        #[command(flatten)]
+        output: Output,
+    },
+
+    /// Merge tables, adding the weights of items with the same value
+    Merge {
+        /// The tables to merge, where `-` reads from stdin
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        #[command(flatten)]
+        options: InputOptions,
+
+        #[command(flatten)]
+        output: Output,
+    },
+
+    /// Show the items added, removed or reweighted between two tables
+    Diff {
+        /// The original table, where `-` reads from stdin
+        old: PathBuf,
+
+        /// The changed table, where `-` reads from stdin
+        new: PathBuf,
+
+        #[command(flatten)]
+        options: InputOptions,
+    },
+}
+
+#[derive(Args)]
+struct Input
+{
+    /// The table to read, where `-` reads from stdin
+    #[arg(default_value = "-")]
+    file: PathBuf,
+
+    #[command(flatten)]
+    options: InputOptions,
+}
+
+#[derive(Args)]
+struct InputOptions
+{
+    /// Format of input tables, inferred from the file extension if not given, otherwise `text`
+    #[arg(short, long)]
+    format: Option<Format>,
+
+    /// CSV column to read weights from, by header name or index
+    #[arg(long, default_value = "weight")]
+    weight_column: String,
+
+    /// CSV column to read values from, by header name or index
+    #[arg(long, default_value = "value")]
+    value_column: String,
+}
+
+#[derive(Args)]
+struct Output
+{
+    /// Format to write the table in
+    #[arg(long, default_value = "text")]
+    to: Format,
+}
+
+#[derive(ValueEnum, Clone, Copy, PartialEq, Debug)]
+enum Format
+{
+    /// One item per line, such as `5  shard`
+    Text,
+    /// The JSON interchange format
+    Json,
+    /// CSV with `weight` and `value` columns
+    Csv,
+    /// TSV with `weight` and `value` columns
+    Tsv,
+}
+
+
+fn main() -> ExitCode
+{
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+
+        // output was piped into something like `head`, which exited early
+        Err(err) if err.downcast_ref::<io::Error>().is_some_and(|err| err.kind() == io::ErrorKind::BrokenPipe) => ExitCode::SUCCESS,
+
+        Err(err) => {
+            eprintln!("wlist: {err}");
+            ExitCode::FAILURE
+        },
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>>
+{
+    let mut stdout = io::stdout().lock();
+
+    match cli.command {
+        Command::Sample { input, count, seed, unique, no_replace } => {
+            let table = read_table(&input.file, &input.options)?;
+
+            let mut rng = match seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None       => StdRng::from_os_rng(),
+            };
+
+            let values =
+                if unique {
+                    table.select_random_values_unique()
+                        .rng(&mut rng)
+                        .count(count)
+                        .call()
+                }
+                else {
+                    table.select_random_values()
+                        .rng(&mut rng)
+                        .count(count)
+                        .replace(!no_replace)
+                        .call()
+                };
+
+            for value in values {
+                writeln!(stdout, "{value}")?;
+            }
+        },
+
+        Command::Stats { input } => {
+            let table = read_table(&input.file, &input.options)?;
+            let probabilities = table.normalised()?;
+
+            writeln!(stdout, "items:   {}", table.total_items())?;
+            writeln!(stdout, "total:   {}", table.len())?;
+            writeln!(stdout, "entropy: {:.4} bits", table.entropy()?)?;
+
+            if !table.is_empty() {
+                writeln!(stdout)?;
+
+                let width = table.weights().map(|weight| weight.to_string().len()).max().unwrap_or(0).max(6);
+                writeln!(stdout, "{:<width$}  probability  value", "weight")?;
+
+                for (item, prob) in table.iter().zip(probabilities.weights()) {
+                    writeln!(stdout, "{:<width$}  {:<11.4}  {}", item.weight, prob, item.value)?;
+                }
+            }
+        },
+
+        Command::Normalise { input, output } => {
+            let table = read_table(&input.file, &input.options)?;
+            write_table(&mut stdout, &table.normalised()?, output.to)?;
+        },
+
+        Command::Merge { files, options, output } => {
+            let mut merged = Table::new();
+
+            for file in &files {
+                merged.merge_with(read_table(file, &options)?);
+            }
+
+            write_table(&mut stdout, &merged, output.to)?;
+        },
+
+        Command::Diff { old, new, options } => {
+            let old = WeightedMap::from(read_table(&old, &options)?);
+            let new = WeightedMap::from(read_table(&new, &options)?);
+
+            for (weight, value) in old.raw() {
+                match new.weight_of(value) {
+                    None => writeln!(stdout, "- {weight}  {value}")?,
+                    Some(new_weight) if new_weight != weight => writeln!(stdout, "~ {weight} -> {new_weight}  {value}")?,
+                    Some(_) => {},
+                }
+            }
+
+            for (weight, value) in new.raw() {
+                if !old.contains_value(value) {
+                    writeln!(stdout, "+ {weight}  {value}")?;
+                }
+            }
+        },
+    }
+
+    Ok(())
+}
+
+
+// == INPUT == //
+fn read_table(path: &Path, options: &InputOptions) -> Result<Table, Box<dyn Error>>
+{
+    let format = options.format
+        .or_else(|| infer_format(path))
+        .unwrap_or(Format::Text);
+
+    let (reader, name): (Box<dyn Read>, _) =
+        if path == Path::new("-") {
+            (Box::new(io::stdin().lock()), "stdin".into())
+        } else {
+            let file = File::open(path).map_err(|err| format!("Failed to open {}: {err}", path.display()))?;
+            (Box::new(file), path.display().to_string())
+        };
+
+    parse_table(reader, format, options)
+        .map_err(|err| format!("Failed to read {name}: {err}").into())
+}
+
+fn infer_format(path: &Path) -> Option<Format>
+{
+    match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+        "json" => Some(Format::Json),
+        "csv"  => Some(Format::Csv),
+        "tsv"  => Some(Format::Tsv),
+        "txt"  => Some(Format::Text),
+        _ => None,
+    }
+}
+
+fn parse_table(mut reader: impl Read, format: Format, options: &InputOptions) -> Result<Table, Box<dyn Error>>
+{
+    match format {
+        Format::Text => {
+            let mut source = String::new();
+            reader.read_to_string(&mut source)?;
+            Ok(source.parse()?)
+        },
+
+        Format::Json => {
+            let table = WeightedList::<serde_json::Value, f64>::read_interchange(reader)?;
+
+            Ok(table.mapped_values(|value| match value {
+                serde_json::Value::String(value) => value.clone(),
+                value => value.to_string(),
+            }))
+        },
+
+        Format::Csv | Format::Tsv => {
+            let column = |name: &str| name.parse::<usize>().map_or_else(|_| CsvColumn::from(name), CsvColumn::from);
+
+            WeightedList::from_csv_reader()
+                .reader(reader)
+                .weight_column(column(&options.weight_column))
+                .value_column(column(&options.value_column))
+                .delimiter(if format == Format::Tsv { b'\t' } else { b',' })
+                .value_parser(|value| value.parse::<String>())
+                .call()
+        },
+    }
+}
+
+
+// == OUTPUT == //
+fn write_table(writer: &mut impl Write, table: &Table, format: Format) -> Result<(), Box<dyn Error>>
+{
+    match format {
+        Format::Text => write!(writer, "{table}")?,
+        Format::Json => table.write_interchange(writer)?,
+        Format::Csv  => table.to_csv_writer().writer(writer).call()?,
+        Format::Tsv  => table.to_csv_writer().writer(writer).delimiter(b'\t').call()?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,274 @@
+use std::error::{ Error };
+use std::fmt::{ self, Display };
+use std::io;
+use std::str::{ FromStr };
+
+use bon::bon;
+
+use crate::*;
+use crate::errors::*;
+
+
+/// A column of a CSV file, identified by its header name or its index.
+///
+/// Can be converted from a `&str` or `String` for a name, or a `usize` for an index.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum CsvColumn
+{
+    Name(String),
+    Index(usize),
+}
+
+impl From<&str> for CsvColumn
+{
+    fn from(name: &str) -> Self
+    {
+        Self::Name(name.to_string())
+    }
+}
+
+impl From<String> for CsvColumn
+{
+    fn from(name: String) -> Self
+    {
+        Self::Name(name)
+    }
+}
+
+impl From<usize> for CsvColumn
+{
+    fn from(index: usize) -> Self
+    {
+        Self::Index(index)
+    }
+}
+
+impl Display for CsvColumn
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            Self::Name(name)   => write!(f, "{name:?}"),
+            Self::Index(index) => write!(f, "{index}"),
+        }
+    }
+}
+
+
+// == CSV == //
+/// Methods for reading and writing CSV, which use the bon builder syntax.
+#[bon]
+impl<V, W: Weight> WeightedList<V,W>
+{
+    /// Construct a [`WeightedList`] from CSV data, with one item per record. Any other columns are ignored.
+    ///
+    /// Call this method using `bon` builder syntax (see § Usage below).
+    ///
+    /// # Options
+    ///
+    /// ```text
+    /// reader:        impl io::Read,
+    /// value_parser:  FnMut(&str) -> Result<V, E>,
+    /// weight_column: impl Into<CsvColumn> = "weight" or 0,
+    /// value_column:  impl Into<CsvColumn> = "value" or 1,
+    /// has_headers:   bool = true,
+    /// delimiter:     u8 = b',',
+    /// ```
+    ///
+    /// - `value_parser`: Parses the value of each record from its field.
+    /// - `weight_column`, `value_column` (optional): The columns to read weights and values from, by header name or index. If `has_headers` is `true`, these default to the columns named `weight` and `value`, otherwise to the first and second columns.
+    /// - `has_headers` (optional): Whether the first record is a header row, in which case it is not read as an item.
+    /// - `delimiter` (optional): The field delimiter, such as `b'\t'` for TSV.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let data = "\
+    /// name,rarity,weight
+    /// sup,common,2
+    /// nova,rare,3
+    /// ";
+    ///
+    /// let wl = WeightedList::<String, u32>::from_csv_reader()
+    ///     .reader(data.as_bytes())
+    ///     .value_column("name")
+    ///     .value_parser(|value| value.parse::<String>())
+    ///     .call()?;
+    ///
+    /// assert_eq!( wl, wlist![(2, "sup".to_string()), (3, "nova".to_string())] );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - [`CsvParseError`] if a column is missing, or a weight or value cannot be parsed.
+    /// - [`csv::Error`] if the data is not valid CSV.
+    #[builder]
+    pub fn from_csv_reader<R, E>(
+        reader: R,
+        mut value_parser: impl FnMut(&str) -> Result<V, E>,
+        #[builder(into)] weight_column: Option<CsvColumn>,
+        #[builder(into)] value_column: Option<CsvColumn>,
+        has_headers: Option<bool>,
+        delimiter: Option<u8>,
+    ) -> Result<Self, Box<dyn Error>>
+        where
+            R: io::Read,
+            W: FromStr,
+            E: Display,
+    {
+        let has_headers = has_headers.unwrap_or(true);
+
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(has_headers)
+            .delimiter(delimiter.unwrap_or(b','))
+            .from_reader(reader);
+
+        let (weight_column, value_column) =
+            if has_headers {(
+                weight_column.unwrap_or(CsvColumn::Name("weight".to_string())),
+                value_column.unwrap_or(CsvColumn::Name("value".to_string())),
+            )}
+            else {(
+                weight_column.unwrap_or(CsvColumn::Index(0)),
+                value_column.unwrap_or(CsvColumn::Index(1)),
+            )};
+
+        let headers = if has_headers { Some(reader.headers()?.clone()) } else { None };
+        let weight_idx = _find_column_(&weight_column, headers.as_ref())?;
+        let value_idx = _find_column_(&value_column, headers.as_ref())?;
+
+        let mut out = Self::new();
+
+        for record in reader.records() {
+            let record = record?;
+            let line = record.position().map_or(0, |position| position.line() as usize);
+
+            let error = |column: &CsvColumn, reason: String| CsvParseError { line, column: column.to_string(), reason };
+
+            let weight = record.get(weight_idx)
+                .ok_or_else(|| error(&weight_column, "Missing field".to_string()))?;
+            let weight = weight.trim().parse::<W>()
+                .map_err(|_| error(&weight_column, format!("Invalid weight {weight:?}")))?;
+
+            let value = record.get(value_idx)
+                .ok_or_else(|| error(&value_column, "Missing field".to_string()))?;
+            let value = value_parser(value)
+                .map_err(|err| error(&value_column, format!("Invalid value {value:?}: {err}")))?;
+
+            out.push_new_item(weight, value);
+        }
+
+        Ok(out)
+    }
+
+    /// Write the list as CSV, with one record per item.
+    ///
+    /// Call this method using `bon` builder syntax (see § Usage below).
+    ///
+    /// # Options
+    ///
+    /// ```text
+    /// writer:      impl io::Write,
+    /// has_headers: bool = true,
+    /// probability: bool = false,
+    /// cumulative:  bool = false,
+    /// delimiter:   u8 = b',',
+    /// ```
+    ///
+    /// - `has_headers` (optional): Whether to write a header row of `weight`, `value` and any computed columns.
+    /// - `probability` (optional): Whether to add a `probability` column, with the probability of selecting each item.
+    /// - `cumulative` (optional): Whether to add a `cumulative` column, with the running total of weights up to and including each item.
+    /// - `delimiter` (optional): The field delimiter, such as `b'\t'` for TSV.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    /// let mut out = Vec::new();
+    ///
+    /// wl.to_csv_writer()
+    ///     .writer(&mut out)
+    ///     .probability(true)
+    ///     .cumulative(true)
+    ///     .call()?;
+    ///
+    /// assert_eq!(
+    ///     String::from_utf8(out)?,
+    ///     "weight,value,probability,cumulative\n2,sup,0.2,2\n3,nova,0.3,5\n5,shard,0.5,10\n"
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - If the total weight of the list is zero, probabilities are written as `NaN`.
+    #[builder]
+    pub fn to_csv_writer<Wr>(&self,
+        writer: Wr,
+        has_headers: Option<bool>,
+        probability: Option<bool>,
+        cumulative: Option<bool>,
+        delimiter: Option<u8>,
+    ) -> Result<(), Box<dyn Error>>
+        where
+            Wr: io::Write,
+            V: Display,
+            W: Display,
+    {
+        let probability = probability.unwrap_or(false);
+        let cumulative = cumulative.unwrap_or(false);
+
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(delimiter.unwrap_or(b','))
+            .from_writer(writer);
+
+        if has_headers.unwrap_or(true) {
+            let mut headers = vec!["weight", "value"];
+            if probability { headers.push("probability") }
+            if cumulative  { headers.push("cumulative") }
+
+            writer.write_record(headers)?;
+        }
+
+        let total: f64 = util::try_cast(self.len())?;
+        let mut running = W::zero();
+
+        for (weight, value) in self.raw() {
+            let mut record = vec![weight.to_string(), value.to_string()];
+
+            if probability {
+                let weight: f64 = util::try_cast(weight)?;
+                record.push((weight / total).to_string());
+            }
+
+            if cumulative {
+                running += weight;
+                record.push(running.to_string());
+            }
+
+            writer.write_record(record)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Find the index of `column` in `headers`, which is `None` if there is no header row.
+fn _find_column_(column: &CsvColumn, headers: Option<&csv::StringRecord>) -> Result<usize, CsvParseError>
+{
+    let error = |reason: &str| CsvParseError { line: 1, column: column.to_string(), reason: reason.to_string() };
+
+    match (column, headers) {
+        (CsvColumn::Index(index), _) => Ok(*index),
+        (CsvColumn::Name(name), Some(headers)) => {
+            headers.iter()
+                .position(|header| header.trim() == name)
+                .ok_or_else(|| error("Column not found in headers"))
+        },
+        (CsvColumn::Name(_), None) => Err(error("Columns can only be found by name if there is a header row")),
+    }
+}
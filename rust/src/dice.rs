@@ -0,0 +1,516 @@
+use std::collections::{ BTreeMap };
+use std::error::{ Error };
+
+use crate::*;
+use crate::errors::*;
+
+
+/// The maximum number of times an exploding die may explode, unless specified otherwise.
+const DEFAULT_EXPLODE_DEPTH: u32 = 3;
+
+/// The maximum number of dice in a group, such as the `4` in `4d6`.
+const MAX_DICE: u32 = 100;
+
+/// The maximum number of sides on a die.
+const MAX_SIDES: u32 = 1000;
+
+/// The maximum number of outcomes of a single die, which for an exploding die is `sides * (explode_depth + 1)`.
+const MAX_DIE_OUTCOMES: u64 = 10_000;
+
+
+// == CONSTRUCTORS == //
+/// Methods for constructing distributions from dice notation.
+impl<W: Weight> WeightedList<i64, W>
+{
+    /// Construct a [`WeightedList`] of the exact distribution of outcomes for rolling dice described in standard dice notation. Items are ordered by ascending outcome, and weights are the number of ways each outcome can occur.
+    ///
+    /// Notation consists of terms joined by `+` or `-`, where each term is either a constant such as `2`, or dice such as `3d6`. Dice may be followed by modifiers:
+    ///
+    /// | modifier | meaning |
+    /// | :------- | :------ |
+    /// | `d%` | a 100-sided die |
+    /// | `kh2`, `k2`, `keep highest 2` | keep the highest 2 dice |
+    /// | `kl2`, `keep lowest 2` | keep the lowest 2 dice |
+    /// | `!` | exploding dice, which are rolled again and added when they land on their highest face |
+    ///
+    /// Omitting the number of dice or dice kept defaults to `1`. Letters are case-insensitive and whitespace is ignored.
+    ///
+    /// Exploding dice explode at most 3 times. To change this limit, use [`::from_dice_with_depth()`](Self::from_dice_with_depth).
+    ///
+    /// To keep the work bounded, a group may have at most 100 dice, each with at most 1000 sides. An exploding die may have at most 10000 outcomes, counting each face for every time it can explode, so `sides * (explode_depth + 1)` must not exceed 10000.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = WeightedList::<i64, u32>::from_dice("2d4+1")?;
+    /// assert_eq!( wl, wlist![(1, 3), (2, 4), (3, 5), (4, 6), (3, 7), (2, 8), (1, 9)] );
+    ///
+    /// let wl = WeightedList::<i64, u32>::from_dice("2d20 keep highest")?;
+    /// assert_eq!( wl.total_items(), 20 );
+    /// assert_eq!( wl.len(), 400 );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - [`DiceParseError`] if `notation` is invalid or exceeds the limits above.
+    /// - [`WeightOverflow`] if the number of ways an outcome can occur exceeds the range of `W`.
+    pub fn from_dice(notation: &str) -> Result<Self, Box<dyn Error>>
+    {
+        Self::from_dice_with_depth(notation, DEFAULT_EXPLODE_DEPTH)
+    }
+
+    /// Construct a [`WeightedList`] of the exact distribution of outcomes for rolling dice described in standard dice notation, allowing each exploding die to explode at most `explode_depth` times.
+    ///
+    /// See [`::from_dice()`](Self::from_dice) for details.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = WeightedList::<i64, u32>::from_dice_with_depth("d4!", 1)?;
+    ///
+    /// assert_eq!(
+    ///     wl,
+    ///     wlist![(4, 1), (4, 2), (4, 3), (1, 5), (1, 6), (1, 7), (1, 8)]
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_dice_with_depth(notation: &str, explode_depth: u32) -> Result<Self, Box<dyn Error>>
+    {
+        let terms = DiceParser::new(notation, explode_depth).parse()?;
+
+        let mut total = BTreeMap::from([(0, W::one())]);
+
+        for (sign, term) in terms {
+            let outcomes = match term {
+                Term::Constant(value) => vec![(value, W::one())],
+                Term::Dice(dice) => _roll_dice_(&dice, explode_depth)?,
+            };
+
+            let outcomes = outcomes.into_iter()
+                .map(|(value, weight)| (sign * value, weight))
+                .collect::<Vec<_>>();
+
+            total = _convolve_(&total, &outcomes)?;
+        }
+
+        Ok(
+            total.into_iter()
+                .map(|(value, weight)| (weight, value))
+                .collect()
+        )
+    }
+}
+
+
+// == PARSING == //
+/// A term in dice notation.
+#[derive(Debug, PartialEq)]
+enum Term
+{
+    Constant(i64),
+    Dice(Dice),
+}
+
+/// A group of identical dice, such as `4d6kh3`.
+#[derive(Debug, PartialEq)]
+struct Dice
+{
+    count: u32,
+    sides: u32,
+    keep: Option<Keep>,
+    explode: bool,
+}
+
+/// Which dice to keep from a group of dice.
+#[derive(Debug, PartialEq)]
+enum Keep
+{
+    Highest(u32),
+    Lowest(u32),
+}
+
+/// A parser for dice notation, producing signed [`Term`]s.
+struct DiceParser<'s>
+{
+    notation: &'s str,
+    bytes: &'s [u8],
+    pos: usize,
+    explode_depth: u32,
+}
+
+impl<'s> DiceParser<'s>
+{
+    fn new(notation: &'s str, explode_depth: u32) -> Self
+    {
+        Self { notation, bytes: notation.as_bytes(), pos: 0, explode_depth }
+    }
+
+    fn error(&self, position: usize, reason: &'static str) -> DiceParseError
+    {
+        DiceParseError { notation: self.notation.to_string(), position, reason }
+    }
+
+    fn skip_whitespace(&mut self)
+    {
+        while self.bytes.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn is_done(&self) -> bool
+    {
+        self.pos >= self.bytes.len()
+    }
+
+    /// Consume `word` if it comes next, ignoring case.
+    fn eat(&mut self, word: &str) -> bool
+    {
+        let end = self.pos + word.len();
+
+        if self.bytes.get(self.pos..end).is_some_and(|next| next.eq_ignore_ascii_case(word.as_bytes())) {
+            self.pos = end;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume a number if one comes next.
+    fn number(&mut self) -> Result<Option<u32>, DiceParseError>
+    {
+        let start = self.pos;
+
+        while self.bytes.get(self.pos).is_some_and(u8::is_ascii_digit) {
+            self.pos += 1;
+        }
+
+        if start == self.pos {
+            return Ok(None);
+        }
+
+        self.notation[start..self.pos]
+            .parse()
+            .map(Some)
+            .map_err(|_| self.error(start, "Number is too large"))
+    }
+
+    fn parse(mut self) -> Result<Vec<(i64, Term)>, DiceParseError>
+    {
+        let mut terms = Vec::new();
+
+        self.skip_whitespace();
+
+        let mut sign = if self.eat("-") { -1 } else { self.eat("+"); 1 };
+
+        loop {
+            self.skip_whitespace();
+            terms.push((sign, self.term()?));
+            self.skip_whitespace();
+
+            if self.is_done() {
+                break;
+            }
+
+            sign =
+                if self.eat("+") { 1 }
+                else if self.eat("-") { -1 }
+                else {
+                    return Err(self.error(self.pos, "Expected `+` or `-`"));
+                };
+        }
+
+        Ok(terms)
+    }
+
+    fn term(&mut self) -> Result<Term, DiceParseError>
+    {
+        let start = self.pos;
+        let count = self.number()?;
+
+        if !self.eat("d") {
+            return count
+                .map(|value| Term::Constant(value as i64))
+                .ok_or_else(|| self.error(start, "Expected dice or a constant"));
+        }
+
+        let count = count.unwrap_or(1);
+
+        if count == 0 {
+            return Err(self.error(start, "Number of dice must be positive"));
+        }
+
+        if count > MAX_DICE {
+            return Err(self.error(start, "Number of dice must be at most 100"));
+        }
+
+        let sides_start = self.pos;
+        let sides =
+            if self.eat("%") { 100 }
+            else {
+                self.number()?.ok_or_else(|| self.error(sides_start, "Expected number of sides"))?
+            };
+
+        if sides == 0 {
+            return Err(self.error(sides_start, "Number of sides must be positive"));
+        }
+
+        if sides > MAX_SIDES {
+            return Err(self.error(sides_start, "Number of sides must be at most 1000"));
+        }
+
+        let mut dice = Dice { count, sides, keep: None, explode: false };
+
+        loop {
+            self.skip_whitespace();
+            let modifier_start = self.pos;
+
+            if self.eat("!") {
+                if dice.explode {
+                    return Err(self.error(modifier_start, "Dice cannot explode more than once"));
+                }
+
+                if sides as u64 * (self.explode_depth as u64 + 1) > MAX_DIE_OUTCOMES {
+                    return Err(self.error(modifier_start, "Exploding dice have too many outcomes for the explosion depth"));
+                }
+
+                dice.explode = true;
+                continue;
+            }
+
+            let highest =
+                if self.eat("keep") {
+                    self.skip_whitespace();
+
+                    if self.eat("highest") { true }
+                    else if self.eat("lowest") { false }
+                    else {
+                        return Err(self.error(self.pos, "Expected `highest` or `lowest`"));
+                    }
+                }
+                else if self.eat("kh") { true }
+                else if self.eat("kl") { false }
+                else if self.eat("k") { true }
+                else {
+                    break;
+                };
+
+            if dice.keep.is_some() {
+                return Err(self.error(modifier_start, "Dice cannot be kept more than once"));
+            }
+
+            self.skip_whitespace();
+            let kept = self.number()?.unwrap_or(1);
+
+            if kept == 0 || kept > count {
+                return Err(self.error(modifier_start, "Number of dice kept must be between 1 and the number of dice"));
+            }
+
+            dice.keep = Some(if highest { Keep::Highest(kept) } else { Keep::Lowest(kept) });
+        }
+
+        Ok(Term::Dice(dice))
+    }
+}
+
+
+// == DISTRIBUTIONS == //
+/// Get the outcomes of rolling a single die, each with the number of ways it can occur.
+///
+/// An exploding die rolled `k` times before stopping on a face below its highest has probability $s^{-(k+1)}$. Scaling all outcomes by $s^{d+1}$ for an explosion limit of $d$ keeps the weights integral.
+fn _roll_die_<W: Weight>(sides: u32, explode_depth: u32) -> Result<Vec<(i64, W)>, Box<dyn Error>>
+{
+    let s = util::try_cast::<u32, W>(sides)?;
+
+    let mut powers = vec![W::one()];
+    for _ in 0..explode_depth {
+        powers.push(util::checked_mul(*powers.last().unwrap(), s)?);
+    }
+
+    let sides = sides as i64;
+    let depth = explode_depth as i64;
+
+    let mut out = Vec::new();
+
+    let outcome = |k: i64, face: i64| {
+        k.checked_mul(sides)
+            .and_then(|total| total.checked_add(face))
+            .ok_or("Dice outcome exceeds the range of i64")
+    };
+
+    for k in 0..depth {
+        for face in 1..sides {
+            out.push((outcome(k, face)?, powers[(depth - k) as usize]));
+        }
+    }
+
+    for face in 1..=sides {
+        out.push((outcome(depth, face)?, W::one()));
+    }
+
+    Ok(out)
+}
+
+/// Get the outcomes of rolling a group of dice, each with the number of ways it can occur.
+fn _roll_dice_<W: Weight>(dice: &Dice, explode_depth: u32) -> Result<Vec<(i64, W)>, Box<dyn Error>>
+{
+    let die = _roll_die_::<W>(dice.sides, if dice.explode { explode_depth } else { 0 })?;
+
+    let Some(keep) = &dice.keep else {
+        let mut total = BTreeMap::from([(0, W::one())]);
+
+        for _ in 0..dice.count {
+            total = _convolve_(&total, &die)?;
+        }
+
+        return Ok(total.into_iter().collect());
+    };
+
+    let (kept, highest) = match *keep {
+        Keep::Highest(kept) => (kept as usize, true),
+        Keep::Lowest(kept) => (kept as usize, false),
+    };
+
+    let mut faces = die;
+    faces.sort_by_key(|&(face, _)| if highest { -face } else { face });
+
+    let count = dice.count as usize;
+
+    /* NOTE: Rather than tracking every sorted roll, go through the faces from most to least preferred, choosing how many of the remaining dice land on each. The first `kept` dice assigned are the ones kept, so only the number of dice assigned and the sum of those kept need tracking. */
+    let mut states = BTreeMap::from([((0_usize, 0_i64), W::one())]);
+
+    for (i, &(face, face_weight)) in faces.iter().enumerate() {
+        let last = i == faces.len() - 1;
+        let mut next = BTreeMap::new();
+
+        for (&(rolled, sum), &weight) in &states {
+            let remaining = count - rolled;
+            let mut power = W::one();
+
+            for landed in 0..=remaining {
+                // the least preferred face takes all remaining dice
+                if !last || landed == remaining {
+                    let newly_kept = landed.min(kept.saturating_sub(rolled));
+                    let ways = util::checked_mul(_binomial_::<W>(remaining, landed)?, power)?;
+
+                    let entry = next.entry((rolled + landed, sum + newly_kept as i64 * face)).or_insert(W::zero());
+                    *entry = util::checked_add(*entry, util::checked_mul(weight, ways)?)?;
+                }
+
+                if landed < remaining {
+                    power = util::checked_mul(power, face_weight)?;
+                }
+            }
+        }
+
+        states = next;
+    }
+
+    let mut out = BTreeMap::new();
+
+    for ((_, sum), weight) in states {
+        let entry = out.entry(sum).or_insert(W::zero());
+        *entry = util::checked_add(*entry, weight)?;
+    }
+
+    Ok(out.into_iter().collect())
+}
+
+/// Get the number of ways to choose `k` of `n` dice.
+fn _binomial_<W: Weight>(n: usize, k: usize) -> Result<W, Box<dyn Error>>
+{
+    let k = k.min(n - k);
+    let mut out: u128 = 1;
+
+    for i in 0..k {
+        out = out.checked_mul((n - i) as u128)
+            .ok_or_else(|| WeightOverflow {
+                operation: "multiplying",
+                lhs: out.to_string(),
+                rhs: (n - i).to_string(),
+            })?
+            / (i + 1) as u128;
+    }
+
+    Ok(util::try_cast::<u128, W>(out)?)
+}
+
+/// Convolve a distribution of outcomes with another, summing outcomes and multiplying weights.
+fn _convolve_<W: Weight>(
+    lhs: &BTreeMap<i64, W>,
+    rhs: &[(i64, W)],
+) -> Result<BTreeMap<i64, W>, WeightOverflow>
+{
+    let mut out = BTreeMap::new();
+
+    for (&l_value, &l_weight) in lhs {
+        for &(r_value, r_weight) in rhs {
+            let weight = util::checked_mul(l_weight, r_weight)?;
+            let entry = out.entry(l_value + r_value).or_insert(W::zero());
+            *entry = util::checked_add(*entry, weight)?;
+        }
+    }
+
+    Ok(out)
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn parse(notation: &str) -> Result<Vec<(i64, Term)>, DiceParseError>
+    {
+        DiceParser::new(notation, DEFAULT_EXPLODE_DEPTH).parse()
+    }
+
+    #[test] fn parse_terms()
+    {
+        assert_eq!(
+            parse("3d6+2").unwrap(),
+            vec![
+                (1, Term::Dice(Dice { count: 3, sides: 6, keep: None, explode: false })),
+                (1, Term::Constant(2)),
+            ]
+        );
+
+        assert_eq!(
+            parse(" -1 + D% - 4d6 KH3! ").unwrap(),
+            vec![
+                (-1, Term::Constant(1)),
+                (1, Term::Dice(Dice { count: 1, sides: 100, keep: None, explode: false })),
+                (-1, Term::Dice(Dice { count: 4, sides: 6, keep: Some(Keep::Highest(3)), explode: true })),
+            ]
+        );
+
+        assert_eq!(
+            parse("2d20 keep lowest").unwrap(),
+            vec![(1, Term::Dice(Dice { count: 2, sides: 20, keep: Some(Keep::Lowest(1)), explode: false }))]
+        );
+    }
+
+    #[test] fn parse_errors()
+    {
+        for (notation, position) in [
+            ("", 0),
+            ("3d", 2),
+            ("0d6", 0),
+            ("2d0", 2),
+            ("3d6 +", 5),
+            ("3d6 2", 4),
+            ("2d6kh3", 3),
+            ("2d6kh1kl1", 6),
+            ("d6!!", 3),
+            ("d6 keep most", 8),
+            ("99999999999d6", 0),
+            ("1000000d1000", 0),
+            ("d4294967295", 1),
+        ] {
+            let err = parse(notation).unwrap_err();
+            assert_eq!( err.position, position, "{notation:?}" );
+        }
+    }
+}
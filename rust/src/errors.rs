@@ -42,3 +42,270 @@ impl Display for EmptyWeightedList
 }
 
 impl Error for EmptyWeightedList {}
+
+
+/// A method received collections of weights and values with differing lengths, so they cannot be paired into items.
+#[derive(Debug)]
+pub struct LengthMismatch
+{
+    pub(crate) values: usize,
+    pub(crate) weights: usize,
+}
+
+impl Display for LengthMismatch
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "Received {} values but {} weights", self.values, self.weights)
+    }
+}
+
+impl Error for LengthMismatch {}
+
+
+/// An arithmetic operation on two weights overflowed the range of the weight type, so the exact result cannot be represented.
+#[derive(Debug)]
+pub struct WeightOverflow
+{
+    pub(crate) operation: &'static str,
+    pub(crate) lhs: String,
+    pub(crate) rhs: String,
+}
+
+impl Display for WeightOverflow
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "Overflow when {} weights {} and {}", self.operation, self.lhs, self.rhs)
+    }
+}
+
+impl Error for WeightOverflow {}
+
+
+/// A string of dice notation could not be parsed, such as by [`WeightedList::from_dice()`](crate::WeightedList::from_dice).
+#[derive(Debug)]
+pub struct DiceParseError
+{
+    pub(crate) notation: String,
+    pub(crate) position: usize,
+    pub(crate) reason: &'static str,
+}
+
+impl Display for DiceParseError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "Invalid dice notation {:?} at position {}: {}", self.notation, self.position, self.reason)
+    }
+}
+
+impl Error for DiceParseError {}
+
+
+/// A Bayesian update gave every hypothesis a posterior weight of zero, or some hypothesis a negative weight, so the posterior cannot be normalised.
+#[derive(Debug)]
+pub struct ZeroEvidence;
+
+impl Display for ZeroEvidence
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "Every posterior weight is zero or some are negative, so the posterior cannot be normalised")
+    }
+}
+
+impl Error for ZeroEvidence {}
+
+
+/// An iterative computation, such as [`MarkovChain::stationary_distribution()`](crate::MarkovChain::stationary_distribution), did not converge within its limit of iterations.
+#[derive(Debug)]
+pub struct NoConvergence
+{
+    pub(crate) iterations: usize,
+}
+
+impl Display for NoConvergence
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "Failed to converge within {} iterations", self.iterations)
+    }
+}
+
+impl Error for NoConvergence {}
+
+
+/// A [`Template`](crate::Template) or [`Grammar`](crate::Grammar) could not be parsed.
+#[derive(Debug)]
+pub struct GrammarParseError
+{
+    pub(crate) line: Option<usize>,
+    pub(crate) column: usize,
+    pub(crate) reason: &'static str,
+}
+
+impl Display for GrammarParseError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self.line {
+            Some(line) => write!(f, "Invalid grammar at line {}, column {}: {}", line, self.column, self.reason),
+            None       => write!(f, "Invalid template at column {}: {}", self.column, self.reason),
+        }
+    }
+}
+
+impl Error for GrammarParseError {}
+
+
+/// A [`Grammar`](crate::Grammar) failed to expand a symbol.
+#[derive(Debug)]
+pub struct GrammarExpansionError
+{
+    pub(crate) symbol: String,
+    pub(crate) reason: &'static str,
+}
+
+impl Display for GrammarExpansionError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "Failed to expand symbol {:?}: {}", self.symbol, self.reason)
+    }
+}
+
+impl Error for GrammarExpansionError {}
+
+
+/// A [`WeightedList`](crate::WeightedList) could not be parsed from its text format.
+#[derive(Debug)]
+pub struct ListParseError
+{
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    pub(crate) reason: &'static str,
+}
+
+impl Display for ListParseError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "Invalid weighted list at line {}, column {}: {}", self.line, self.column, self.reason)
+    }
+}
+
+impl Error for ListParseError {}
+
+
+/// A record of CSV data could not be read as an item, such as by [`WeightedList::from_csv_reader()`](crate::WeightedList::from_csv_reader).
+#[derive(Debug)]
+pub struct CsvParseError
+{
+    pub(crate) line: usize,
+    pub(crate) column: String,
+    pub(crate) reason: String,
+}
+
+impl Display for CsvParseError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "Invalid CSV at line {}, column {}: {}", self.line, self.column, self.reason)
+    }
+}
+
+impl Error for CsvParseError {}
+
+
+/// A document in the JSON interchange format could not be read, such as by [`WeightedList::read_interchange()`](crate::WeightedList::read_interchange).
+#[derive(Debug)]
+pub struct InterchangeError
+{
+    pub(crate) reason: String,
+}
+
+impl Display for InterchangeError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "Invalid interchange document: {}", self.reason)
+    }
+}
+
+impl Error for InterchangeError {}
+
+
+/// A file is not in the binary format read by [`MappedFrozenWeightedList`](crate::MappedFrozenWeightedList), or a list cannot be written in it.
+#[derive(Debug)]
+pub struct BinaryFormatError
+{
+    pub(crate) reason: String,
+}
+
+impl Display for BinaryFormatError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "Invalid binary weighted list: {}", self.reason)
+    }
+}
+
+impl Error for BinaryFormatError {}
+
+
+/// A [`LootConfig`](crate::LootConfig) could not be parsed, or is invalid.
+#[derive(Debug)]
+pub struct LootConfigError
+{
+    pub(crate) location: String,
+    pub(crate) reason: String,
+}
+
+impl Display for LootConfigError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "Invalid loot config at {}: {}", self.location, self.reason)
+    }
+}
+
+impl Error for LootConfigError {}
+
+
+/// JSON could not be imported as a [`MinecraftLootTable`](crate::MinecraftLootTable).
+#[derive(Debug)]
+pub struct MinecraftImportError
+{
+    pub(crate) location: String,
+    pub(crate) reason: String,
+}
+
+impl Display for MinecraftImportError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "Invalid Minecraft loot table at {}: {}", self.location, self.reason)
+    }
+}
+
+impl Error for MinecraftImportError {}
+
+
+/// A table could not be loaded into a [`TableRegistry`](crate::TableRegistry).
+#[derive(Debug)]
+pub struct TableLoadError
+{
+    pub(crate) path: std::path::PathBuf,
+    pub(crate) reason: String,
+}
+
+impl Display for TableLoadError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "Failed to load table from {}: {}", self.path.display(), self.reason)
+    }
+}
+
+impl Error for TableLoadError {}
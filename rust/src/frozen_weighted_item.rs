@@ -1,5 +1,6 @@
 use std::{
     fmt,
+    hash::{ Hash, Hasher },
 };
 
 use crate::*;
@@ -8,7 +9,7 @@ use crate::*;
 pub type FWItem<V,W> = FrozenWeightedItem<V,W>;
 
 
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone)]
 pub struct FrozenWeightedItem<V, W: Weight>
 {
     cumulative_weight: W,
@@ -77,10 +78,17 @@ impl<V, W: Weight> FrozenWeightedItem<V,W>
 }
 
 // == CONVERSIONS == //
-impl<V, W: Weight> Into<WeightedItem<V,W>> for FrozenWeightedItem<V,W>
+impl<V, W: Weight> From<FrozenWeightedItem<V,W>> for WeightedItem<V,W>
 {
-    fn into(self) -> WeightedItem<V,W> {
-        WeightedItem::new(self.weight, self.value)
+    fn from(item: FrozenWeightedItem<V,W>) -> Self {
+        WeightedItem::new(item.weight, item.value)
+    }
+}
+
+impl<V, W: Weight> From<FrozenWeightedItem<V,W>> for (W, V)
+{
+    fn from(item: FrozenWeightedItem<V,W>) -> Self {
+        (item.weight, item.value)
     }
 }
 
@@ -93,6 +101,14 @@ impl<V: fmt::Display, W: Weight> fmt::Display for FrozenWeightedItem<V,W>
     }
 }
 
+impl<V: Hash, W: Weight + Hash> Hash for FrozenWeightedItem<V,W>
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.weight.hash(state);
+        self.value.hash(state);
+    }
+}
+
 impl<V: Eq, W: Weight> Eq for FrozenWeightedItem<V,W> {}
 
 impl<V: PartialEq, W: Weight> PartialEq for FrozenWeightedItem<V,W>
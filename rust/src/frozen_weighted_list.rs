@@ -2,7 +2,7 @@ use std::*;
 
 use itertools::Itertools;
 
-use crate::root::*;
+use crate::*;
 use crate::frozen_weighted_item::FrozenWeightedItem;
 
 
@@ -87,6 +87,36 @@ impl<V, W: Weight> FrozenWeightedList<V,W>
     {
         self.values().collect_vec()
     }
+
+    pub fn raw(&self) -> impl Iterator<Item = (W, &V)>
+    {
+        self.data.iter().map(|item| (item.weight(), item.value()))
+    }
+
+    pub fn collect_raw(&self) -> Vec<(W, &V)>
+    {
+        self.raw().collect_vec()
+    }
+
+    pub fn into_raw(self) -> Vec<(W, V)>
+    {
+        self.data.into_iter().map(Into::into).collect_vec()
+    }
+
+    pub fn unique_values(&self) -> impl Iterator<Item = &V>
+        where V: Eq
+    {
+        self.data.iter()
+            .enumerate()
+            .filter(|(i, item)| !self.data[..*i].iter().any(|prev| prev.value() == item.value()))
+            .map(|(_, item)| item.value())
+    }
+
+    pub fn unique_values_hashed(&self) -> impl Iterator<Item = &V>
+        where V: Eq + hash::Hash
+    {
+        self.values().unique()
+    }
 }
 
 // == PROPERTIES == //
@@ -124,10 +154,10 @@ impl<V, W: Weight> From<Vec<FrozenWeightedItem<V,W>>> for FrozenWeightedList<V,W
     }
 }
 
-impl<V, W: Weight> Into<Vec<FrozenWeightedItem<V,W>>> for FrozenWeightedList<V,W>
+impl<V, W: Weight> From<FrozenWeightedList<V,W>> for Vec<FrozenWeightedItem<V,W>>
 {
-    fn into(self) -> Vec<FrozenWeightedItem<V,W>> {
-        self.data
+    fn from(list: FrozenWeightedList<V,W>) -> Self {
+        list.data
     }
 }
 
@@ -148,14 +178,23 @@ impl<V, W: Weight> ops::Deref for FrozenWeightedList<V,W>
 }
 
 // == TRAITS == //
+/// Write the list in the same text format as [`WeightedList`].
 impl<V: fmt::Display, W: Weight> fmt::Display for FrozenWeightedList<V,W>
     where W: fmt::Display
 {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {            
-        write!(f,
-            "FrozenWeightedList[{}]",
-            self.data.iter().map(|item| item.to_string()).join(", ")
-        )
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        crate::text::write_items(f, self.raw())
+    }
+}
+
+/// Parse a list from the same text format as [`WeightedList`], additionally requiring weights to be non-negative and sum without overflowing.
+impl<V: str::FromStr, W: Weight> str::FromStr for FrozenWeightedList<V,W>
+    where W: str::FromStr
+{
+    type Err = crate::errors::ListParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        crate::text::parse_items(source, true).map(Self::init)
     }
 }
 
@@ -0,0 +1,645 @@
+use std::collections::{ HashMap };
+use std::error::{ Error };
+use std::fmt::{ self, Display };
+use std::str::{ FromStr };
+
+use rand::prelude::*;
+
+use crate::*;
+use crate::errors::*;
+
+
+/// The maximum depth of nested symbols a [`Grammar`] expands, unless specified otherwise.
+const DEFAULT_MAX_DEPTH: usize = 32;
+
+
+/// A template for generating text, consisting of literal text interspersed with symbols to expand from a [`Grammar`].
+///
+/// | syntax | meaning |
+/// | :----- | :------ |
+/// | `#noun#` | expand the symbol `noun` |
+/// | `#noun.s#` | expand `noun`, then apply modifiers |
+/// | `[hero:#name#]` | expand `#name#` and bind the result to `hero`, so later `#hero#` gives the same text |
+/// | `\#` | a literal `#`, and likewise for any other character |
+///
+/// The available modifiers are:
+///
+/// - `capitalise` (or `capitalize`) to capitalise the first letter
+/// - `s` (or `plural`) to pluralise
+/// - `a` to prefix the indefinite article `a` or `an`
+///
+/// # Usage
+///
+/// ```
+/// # use weighted_list::*;
+/// let template = Template::parse("[hero:#name#]#hero.capitalise# met #animal.a#.")?;
+/// assert_eq!( template.as_str(), "[hero:#name#]#hero.capitalise# met #animal.a#." );
+///
+/// assert!( Template::parse("#unclosed").is_err() );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Template
+{
+    source: String,
+    segments: Vec<Segment>,
+}
+
+/// A segment of a parsed [`Template`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+enum Segment
+{
+    Text(String),
+    Symbol {
+        name: String,
+        modifiers: Vec<Modifier>,
+    },
+    Bind {
+        variable: String,
+        segments: Vec<Segment>,
+    },
+}
+
+/// A modifier applied to the expansion of a symbol.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Modifier
+{
+    Capitalise,
+    Plural,
+    Article,
+}
+
+impl Template
+{
+    /// Parse a [`Template`] from its `source` text.
+    pub fn parse(source: &str) -> Result<Self, GrammarParseError>
+    {
+        let chars = source.chars().collect::<Vec<_>>();
+
+        Ok(Self {
+            source: source.to_string(),
+            segments: _parse_segments_(&chars, 0)?,
+        })
+    }
+
+    /// Get the source text of the template.
+    pub fn as_str(&self) -> &str
+    {
+        &self.source
+    }
+}
+
+impl FromStr for Template
+{
+    type Err = GrammarParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err>
+    {
+        Self::parse(source)
+    }
+}
+
+impl Display for Template
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}", self.source)
+    }
+}
+
+
+/// A stochastic grammar for generating text, mapping each symbol to a [`WeightedList`] of alternative [`Template`]s.
+///
+/// Expanding a symbol selects one of its alternatives using weighted randomisation, then expands any symbols within that, and so on. Variables bound with `[variable:template]` last for the rest of the expansion.
+///
+/// # Usage
+///
+/// ```
+/// # use weighted_list::*;
+/// let grammar: Grammar<u32> = "
+///     origin: #adjective.capitalise# #noun.s# lurk here.
+///     adjective:
+///         shiny
+///         3 | dull
+///     noun:
+///         wolf
+///         2 | box
+/// ".parse()?;
+///
+/// let text = grammar.expand(&mut rand::rng(), "origin")?;
+/// assert!( text.ends_with("s lurk here.") );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Format
+///
+/// Rules are written in a simple text format, as shown above:
+///
+/// - A line `symbol:` starts the rule for `symbol`. An alternative may follow on the same line.
+/// - Lines below indented further than `symbol:` give further alternatives for that rule.
+/// - An alternative is written as `weight | template`, or just `template` for a weight of `1`.
+/// - Blank lines and lines starting with `//` are ignored.
+///
+/// Symbols with multiple rules have their alternatives combined.
+#[derive(Clone, Debug)]
+pub struct Grammar<W: Weight>
+{
+    rules: HashMap<String, WeightedList<Template, W>>,
+    max_depth: usize,
+}
+
+// == CONSTRUCTORS == //
+impl<W: Weight> Grammar<W>
+{
+    /// Construct an empty [`Grammar`] with no rules.
+    pub fn new() -> Self
+    {
+        Self {
+            rules: HashMap::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Set the rule for `symbol` to the `alternatives`, replacing any existing rule.
+    pub fn set_rule(&mut self, symbol: impl Into<String>, alternatives: WeightedList<Template, W>) -> &mut Self
+    {
+        self.rules.insert(symbol.into(), alternatives);
+        self
+    }
+
+    /// Add an alternative `template` with `weight` to the rule for `symbol`, creating the rule if it does not exist.
+    pub fn add_alternative(&mut self, symbol: impl Into<String>, weight: W, template: Template) -> &mut Self
+    {
+        self.rules.entry(symbol.into())
+            .or_insert_with(WeightedList::new)
+            .push_new_item(weight, template);
+
+        self
+    }
+
+    /// Set the maximum depth of nested symbols to expand before failing, to prevent runaway recursion. Defaults to `32`.
+    pub fn set_max_depth(&mut self, max_depth: usize) -> &mut Self
+    {
+        self.max_depth = max_depth;
+        self
+    }
+}
+
+impl<W: Weight> Default for Grammar<W>
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl<W> FromStr for Grammar<W>
+    where W: Weight + FromStr
+{
+    type Err = GrammarParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err>
+    {
+        let mut out = Self::new();
+        let mut current = None::<(String, usize)>;
+
+        for (i, line) in source.lines().enumerate() {
+            let error = |column, reason| GrammarParseError { line: Some(i + 1), column, reason };
+
+            let content = line.trim();
+            if content.is_empty() || content.starts_with("//") { continue }
+
+            let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+            let is_rule = current.as_ref().is_none_or(|(_, rule_indent)| indent <= *rule_indent);
+
+            let (alternative, column) =
+                if is_rule {
+                    let Some((symbol, rest)) = content.split_once(':') else {
+                        return Err(error(indent + 1, "Expected `symbol:` to start a rule"));
+                    };
+
+                    let symbol = symbol.trim();
+                    if symbol.is_empty() || symbol.contains(|c: char| c.is_whitespace() || "#[]".contains(c)) {
+                        return Err(error(indent + 1, "Symbol names must be nonempty without whitespace, `#`, `[` or `]`"));
+                    }
+
+                    out.rules.entry(symbol.to_string()).or_insert_with(WeightedList::new);
+                    current = Some((symbol.to_string(), indent));
+
+                    let offset = line.chars().count() - rest.chars().count();
+                    let rest_indent = rest.chars().take_while(|c| c.is_whitespace()).count();
+
+                    (rest.trim(), offset + rest_indent + 1)
+                }
+                else {
+                    (content, indent + 1)
+                };
+
+            if alternative.is_empty() { continue }
+
+            let (weight, template, template_column) = match alternative.split_once('|') {
+                Some((weight, template)) if let Ok(weight) = weight.trim().parse::<W>() => {
+                    let offset = weight_prefix_len(alternative, template);
+                    (weight, template.trim_start(), column + offset)
+                },
+                _ => (W::one(), alternative, column),
+            };
+
+            if weight.partial_cmp(&W::zero()).is_none_or(|order| order.is_lt()) {
+                return Err(error(column, "Weights must be non-negative"));
+            }
+
+            let template = Template::parse(template)
+                .map_err(|err| error(template_column + err.column - 1, err.reason))?;
+
+            let (symbol, _) = current.as_ref().expect("a rule has been started");
+            out.add_alternative(symbol.clone(), weight, template);
+        }
+
+        Ok(out)
+    }
+}
+
+/// Get the number of characters in `alternative` before `template`, which follows the weight and `|`, plus any whitespace.
+fn weight_prefix_len(alternative: &str, template: &str) -> usize
+{
+    let trimmed = template.trim_start();
+    alternative.chars().count() - trimmed.chars().count()
+}
+
+// == ACCESSORS == //
+impl<W: Weight> Grammar<W>
+{
+    /// Get the alternatives for `symbol`, or `None` if it has no rule.
+    pub fn rule(&self, symbol: &str) -> Option<&WeightedList<Template, W>>
+    {
+        self.rules.get(symbol)
+    }
+
+    /// Get an iterator over the symbols with rules, in arbitrary order.
+    pub fn symbols(&self) -> impl Iterator<Item = &str>
+    {
+        self.rules.keys().map(String::as_str)
+    }
+
+    /// Get the maximum depth of nested symbols to expand before failing.
+    pub fn max_depth(&self) -> usize
+    {
+        self.max_depth
+    }
+}
+
+// == EXPANSION == //
+impl<W: Weight> Grammar<W>
+{
+    /// Expand `symbol` into text, selecting alternatives using weighted randomisation.
+    ///
+    /// # Errors
+    ///
+    /// - [`GrammarExpansionError`] if a symbol has no rule, or expansion exceeds the maximum depth.
+    /// - [`EmptyWeightedList`] if a symbol has no alternatives to select.
+    pub fn expand<RNG>(&self, rng: &mut RNG, symbol: &str) -> Result<String, Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        let segments = [Segment::Symbol { name: symbol.to_string(), modifiers: Vec::new() }];
+        self._expand_segments_(rng, &segments, 0, &mut HashMap::new())
+    }
+
+    /// Expand the symbols in `template` into text, selecting alternatives using weighted randomisation.
+    ///
+    /// See [`.expand()`](Self::expand) for details.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut grammar = Grammar::<u32>::new();
+    /// grammar.add_alternative("name", 1, Template::parse("ash")?);
+    ///
+    /// let template = Template::parse("[hero:#name#]#hero.capitalise# and #hero.s#")?;
+    ///
+    /// assert_eq!( grammar.expand_template(&mut rand::rng(), &template)?, "Ash and ashes" );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn expand_template<RNG>(&self, rng: &mut RNG, template: &Template) -> Result<String, Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        self._expand_segments_(rng, &template.segments, 0, &mut HashMap::new())
+    }
+
+    fn _expand_segments_<RNG>(&self,
+        rng: &mut RNG,
+        segments: &[Segment],
+        depth: usize,
+        bindings: &mut HashMap<String, String>,
+    ) -> Result<String, Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        let mut out = String::new();
+
+        for segment in segments {
+            match segment {
+                Segment::Text(text) => out.push_str(text),
+
+                Segment::Symbol { name, modifiers } => {
+                    let mut text = match bindings.get(name) {
+                        Some(bound) => bound.clone(),
+                        None => {
+                            let error = |reason| GrammarExpansionError { symbol: name.clone(), reason };
+
+                            if depth >= self.max_depth {
+                                Err(error("Exceeded the maximum depth of nested symbols"))?
+                            }
+
+                            let rule = self.rules.get(name).ok_or_else(|| error("Symbol has no rule"))?;
+                            let template = rule.select_random_value(rng)?;
+
+                            self._expand_segments_(rng, &template.segments, depth + 1, bindings)?
+                        }
+                    };
+
+                    for modifier in modifiers {
+                        text = modifier.apply(&text);
+                    }
+
+                    out.push_str(&text);
+                },
+
+                Segment::Bind { variable, segments } => {
+                    let text = self._expand_segments_(rng, segments, depth, bindings)?;
+                    bindings.insert(variable.clone(), text);
+                },
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+
+// == INTERNAL == //
+impl Modifier
+{
+    fn parse(name: &str) -> Option<Self>
+    {
+        match name {
+            "capitalise" | "capitalize" => Some(Self::Capitalise),
+            "s" | "plural"              => Some(Self::Plural),
+            "a"                         => Some(Self::Article),
+            _                           => None,
+        }
+    }
+
+    fn apply(&self, text: &str) -> String
+    {
+        match self {
+            Self::Capitalise => {
+                let mut chars = text.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None        => String::new(),
+                }
+            },
+
+            Self::Plural => {
+                let lower = text.to_lowercase();
+
+                if ["s", "x", "z", "ch", "sh"].iter().any(|end| lower.ends_with(end)) {
+                    format!("{text}es")
+                }
+                else if lower.ends_with('y') && !lower.chars().rev().nth(1).is_some_and(|c| "aeiou".contains(c)) {
+                    format!("{}ies", &text[..text.len() - 1])
+                }
+                else {
+                    format!("{text}s")
+                }
+            },
+
+            Self::Article => {
+                let vowel = text.chars().next().is_some_and(|c| "aeiouAEIOU".contains(c));
+                format!("{} {text}", if vowel { "an" } else { "a" })
+            },
+        }
+    }
+}
+
+/// Parse `chars` into template segments, where `offset` is the index of `chars` in the full template for error reporting.
+fn _parse_segments_(chars: &[char], offset: usize) -> Result<Vec<Segment>, GrammarParseError>
+{
+    let error = |idx: usize, reason| GrammarParseError { line: None, column: offset + idx + 1, reason };
+
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => {
+                let Some(&escaped) = chars.get(i + 1) else {
+                    return Err(error(i, "Expected a character to escape after `\\`"));
+                };
+
+                text.push(escaped);
+                i += 2;
+            },
+
+            '#' => {
+                let len = chars[i+1..].iter()
+                    .position(|&c| c == '#')
+                    .ok_or_else(|| error(i, "Unclosed `#`"))?;
+
+                let inner = chars[i+1 .. i+1+len].iter().collect::<String>();
+                let mut parts = inner.split('.');
+                let name = parts.next().unwrap_or_default();
+
+                if name.is_empty() {
+                    return Err(error(i, "Expected a symbol name between `#`s"));
+                }
+
+                let modifiers = parts
+                    .map(|part| Modifier::parse(part).ok_or_else(|| error(i, "Unknown modifier")))
+                    .collect::<Result<_,_>>()?;
+
+                if !text.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut text)));
+                }
+                segments.push(Segment::Symbol { name: name.to_string(), modifiers });
+
+                i += len + 2;
+            },
+
+            '[' => {
+                let mut depth = 0;
+                let mut end = None;
+                let mut j = i;
+
+                while j < chars.len() {
+                    match chars[j] {
+                        '\\' => j += 1,
+                        '[' => depth += 1,
+                        ']' => {
+                            depth -= 1;
+                            if depth == 0 { end = Some(j); break }
+                        },
+                        _ => {},
+                    }
+                    j += 1;
+                }
+
+                let end = end.ok_or_else(|| error(i, "Unclosed `[`"))?;
+                let inner = &chars[i+1 .. end];
+
+                let colon = inner.iter()
+                    .position(|&c| c == ':')
+                    .ok_or_else(|| error(i, "Expected `[variable:template]`"))?;
+
+                let variable = inner[..colon].iter().collect::<String>();
+
+                if variable.is_empty() || variable.contains(['#', '[', '.']) {
+                    return Err(error(i, "Variable names must be nonempty without `#`, `[` or `.`"));
+                }
+
+                let nested = _parse_segments_(&inner[colon+1..], offset + i + 1 + colon + 1)?;
+
+                if !text.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut text)));
+                }
+                segments.push(Segment::Bind { variable, segments: nested });
+
+                i = end + 1;
+            },
+
+            ']' => return Err(error(i, "Unmatched `]`")),
+
+            c => {
+                text.push(c);
+                i += 1;
+            },
+        }
+    }
+
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+
+    Ok(segments)
+}
+
+// == SERDE == //
+#[cfg(feature = "serde")]
+impl serde::Serialize for Template
+{
+    /// Serialises as the source text.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Template
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        let source = String::deserialize(deserializer)?;
+        Template::parse(&source).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<W> serde::Serialize for Grammar<W>
+    where W: Weight + serde::Serialize
+{
+    /// Serialises as a struct of the `rules`, mapping each symbol to its alternatives, and the `max_depth`. Symbols are sorted for stable output.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        use serde::ser::SerializeStruct;
+
+        let rules = self.rules.iter().collect::<std::collections::BTreeMap<_,_>>();
+
+        let mut state = serializer.serialize_struct("Grammar", 2)?;
+        state.serialize_field("rules", &rules)?;
+        state.serialize_field("max_depth", &self.max_depth)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, W> serde::Deserialize<'de> for Grammar<W>
+    where W: Weight + serde::Deserialize<'de>
+{
+    /// The `max_depth` may be omitted to use the default.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        #[derive(serde::Deserialize)]
+        struct Raw<W: Weight>
+        {
+            rules: HashMap<String, WeightedList<Template, W>>,
+            max_depth: Option<usize>,
+        }
+
+        let raw = Raw::<W>::deserialize(deserializer)?;
+
+        Ok(Self {
+            rules: raw.rules,
+            max_depth: raw.max_depth.unwrap_or(DEFAULT_MAX_DEPTH),
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test] fn parse_segments()
+    {
+        assert_eq!(
+            Template::parse("a #b.s.capitalise#\\#[c:#d#]").unwrap().segments,
+            vec![
+                Segment::Text("a ".to_string()),
+                Segment::Symbol { name: "b".to_string(), modifiers: vec![Modifier::Plural, Modifier::Capitalise] },
+                Segment::Text("#".to_string()),
+                Segment::Bind {
+                    variable: "c".to_string(),
+                    segments: vec![Segment::Symbol { name: "d".to_string(), modifiers: vec![] }],
+                },
+            ]
+        );
+    }
+
+    #[test] fn parse_errors()
+    {
+        for (template, column) in [
+            ("#a", 1),
+            ("ab ##", 4),
+            ("#a.shout#", 1),
+            ("[a#b#]", 1),
+            ("x [:#b#]", 3),
+            ("[a:#b]", 4),
+            ("[a:#b#", 1),
+            ("a]", 2),
+            ("a\\", 2),
+        ] {
+            let err = Template::parse(template).unwrap_err();
+            assert_eq!( err.column, column, "{template:?}" );
+        }
+    }
+
+    #[test] fn modifiers()
+    {
+        assert_eq!( Modifier::Capitalise.apply("ébène"), "Ébène" );
+        assert_eq!( Modifier::Capitalise.apply(""), "" );
+
+        for (word, plural) in [
+            ("wolf", "wolfs"), ("box", "boxes"), ("church", "churches"),
+            ("fly", "flies"), ("day", "days"), ("glass", "glasses"),
+        ] {
+            assert_eq!( Modifier::Plural.apply(word), plural );
+        }
+
+        assert_eq!( Modifier::Article.apply("owl"), "an owl" );
+        assert_eq!( Modifier::Article.apply("wolf"), "a wolf" );
+    }
+}
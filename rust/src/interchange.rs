@@ -0,0 +1,213 @@
+//! The versioned JSON interchange format shared with the other implementations of `weighted-list`.
+
+use std::error::{ Error };
+use std::io;
+
+use serde::{ Serialize, Deserialize };
+use serde::de::{ DeserializeOwned };
+
+use crate::*;
+use crate::errors::*;
+use crate::repr::{ WeightedCollection };
+
+
+/// The version of the JSON interchange format written by this crate, and the latest version it can read.
+pub const INTERCHANGE_VERSION: u32 = 1;
+
+/// The value of the `format` field, identifying a document as a weighted list.
+const FORMAT: &str = "weighted-list";
+
+
+#[derive(Serialize)]
+struct DocumentRef<'v, V, W>
+{
+    format: &'static str,
+    version: u32,
+    weight_type: &'static str,
+    frozen: bool,
+    items: Vec<Item<&'v V, W>>,
+}
+
+#[derive(Deserialize)]
+struct Document
+{
+    format: String,
+    version: u32,
+    weight_type: String,
+    items: serde_json::Value,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Item<V, W>
+{
+    weight: W,
+    value: V,
+}
+
+
+// == INTERCHANGE == //
+/// Methods for reading and writing the JSON interchange format.
+impl<V, W: Weight> WeightedList<V,W>
+{
+    /// Read a list from a document in the JSON interchange format.
+    ///
+    /// A document is an object with these fields:
+    ///
+    /// ```json
+    /// {
+    ///   "format": "weighted-list",
+    ///   "version": 1,
+    ///   "weight_type": "int",
+    ///   "frozen": false,
+    ///   "items": [
+    ///     { "weight": 2, "value": "sup" },
+    ///     { "weight": 3, "value": "nova" }
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// - `weight_type` is `"int"` or `"float"`. Integer weights can be read into any `W`, but float weights can only be read into a float `W`.
+    /// - Weights must be non-negative.
+    /// - `frozen` is optional, and documents with `"frozen": true` can still be read into a [`WeightedList`].
+    /// - Unknown fields are ignored.
+    ///
+    /// See `SPEC.md` in the repository for the full schema.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let doc = r#"{
+    ///     "format": "weighted-list",
+    ///     "version": 1,
+    ///     "weight_type": "int",
+    ///     "items": [{ "weight": 2, "value": "sup" }, { "weight": 3, "value": "nova" }]
+    /// }"#;
+    ///
+    /// let wl = WeightedList::<String, u32>::read_interchange(doc.as_bytes())?;
+    /// assert_eq!( wl, wlist![(2, "sup".to_string()), (3, "nova".to_string())] );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - [`InterchangeError`] if the document is for a different format or a newer version, has negative weights, or has float weights for an integer `W`.
+    /// - [`serde_json::Error`] if the document is not valid JSON, or an item cannot be deserialised.
+    pub fn read_interchange<R>(reader: R) -> Result<Self, Box<dyn Error>>
+        where
+            R: io::Read,
+            V: DeserializeOwned,
+            W: DeserializeOwned,
+    {
+        _read_(reader)
+    }
+
+    /// Write the list as a document in the JSON interchange format, with `"frozen": false`.
+    ///
+    /// See [`::read_interchange()`](Self::read_interchange) for details of the format.
+    pub fn write_interchange<Wr>(&self, writer: Wr) -> Result<(), Box<dyn Error>>
+        where
+            Wr: io::Write,
+            V: Serialize,
+            W: Serialize,
+    {
+        _write_(self, false, writer)
+    }
+}
+
+/// Methods for reading and writing the JSON interchange format.
+#[cfg(feature = "frozen")]
+impl<V, W: Weight> FrozenWeightedList<V,W>
+{
+    /// Read a list from a document in the JSON interchange format, recomputing cumulative weights.
+    ///
+    /// See [`WeightedList::read_interchange()`] for details of the format. Documents without `"frozen": true` can still be read into a [`FrozenWeightedList`], but weights must also sum without overflowing.
+    pub fn read_interchange<R>(reader: R) -> Result<Self, Box<dyn Error>>
+        where
+            R: io::Read,
+            V: DeserializeOwned,
+            W: DeserializeOwned,
+    {
+        _read_(reader)
+    }
+
+    /// Write the list as a document in the JSON interchange format, with `"frozen": true`.
+    pub fn write_interchange<Wr>(&self, writer: Wr) -> Result<(), Box<dyn Error>>
+        where
+            Wr: io::Write,
+            V: Serialize,
+            W: Serialize,
+    {
+        _write_(self, true, writer)
+    }
+}
+
+
+// == INTERNAL == //
+fn _read_<C, V, W, R>(reader: R) -> Result<C, Box<dyn Error>>
+    where
+        C: WeightedCollection<V,W>,
+        V: DeserializeOwned,
+        W: Weight + DeserializeOwned,
+        R: io::Read,
+{
+    let error = |reason: String| Box::new(InterchangeError { reason });
+
+    let doc: Document = serde_json::from_reader(reader)?;
+
+    if doc.format != FORMAT {
+        Err(error(format!("Expected format {FORMAT:?}, found {:?}", doc.format)))?
+    }
+
+    if doc.version == 0 || doc.version > INTERCHANGE_VERSION {
+        Err(error(format!("Unsupported version {}, expected at most {INTERCHANGE_VERSION}", doc.version)))?
+    }
+
+    let is_integral = util::is_integral::<W>();
+
+    match doc.weight_type.as_str() {
+        "int" => {},
+        "float" if !is_integral => {},
+        "float" => Err(error(format!("Cannot read float weights into integer type {}", std::any::type_name::<W>())))?,
+        other => Err(error(format!("Unknown weight type {other:?}, expected \"int\" or \"float\"")))?,
+    }
+
+    let items: Vec<Item<V,W>> = serde_json::from_value(doc.items)?;
+
+    for (i, item) in items.iter().enumerate() {
+        if item.weight.partial_cmp(&W::zero()).is_none_or(|order| order.is_lt()) {
+            Err(error(format!("Item {i} has weight {:?}, but weights must be non-negative", item.weight)))?
+        }
+
+        if doc.weight_type == "int" && item.weight.to_f64().is_some_and(|weight| weight.fract() != 0.0) {
+            Err(error(format!("Item {i} has weight {:?}, but the weight type is \"int\"", item.weight)))?
+        }
+    }
+
+    let out = C::_serde_from_raw_::<serde_json::Error>(
+        items.into_iter().map(|item| (item.weight, item.value)).collect()
+    )?;
+
+    Ok(out)
+}
+
+fn _write_<C, V, W, Wr>(collection: &C, frozen: bool, mut writer: Wr) -> Result<(), Box<dyn Error>>
+    where
+        C: WeightedCollection<V,W>,
+        V: Serialize,
+        W: Weight + Serialize,
+        Wr: io::Write,
+{
+    let doc = DocumentRef {
+        format: FORMAT,
+        version: INTERCHANGE_VERSION,
+        weight_type: if util::is_integral::<W>() { "int" } else { "float" },
+        frozen,
+        items: collection._serde_raw_().map(|(weight, value)| Item { weight, value }).collect(),
+    };
+
+    serde_json::to_writer_pretty(&mut writer, &doc)?;
+    writeln!(writer)?;
+
+    Ok(())
+}
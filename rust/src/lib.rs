@@ -17,7 +17,7 @@
 //!     println!("{} has weight {}", item.value, item.weight);
 //! }
 //! 
-//! if let Some(result) = wl.select_random_value(&mut rand::rng()) {
+//! if let Ok(result) = wl.select_random_value(&mut rand::rng()) {
 //!     println!("{result}");
 //! }
 //! ```
@@ -36,6 +36,22 @@ pub use weighted_item::{ WeightedItem, WItem };
 mod weighted_list;
 pub use weighted_list::{ WeightedList, WList };
 
+mod weighted_map;
+pub use weighted_map::{ WeightedMap, WMap };
+
+mod mixture;
+pub use mixture::{ Mixture };
+
+mod markov_chain;
+pub use markov_chain::{ MarkovChain, MarkovWalk };
+
+mod grammar;
+pub use grammar::{ Grammar, Template };
+
+mod dice;
+
+mod text;
+
 mod traits;
 pub use traits::{ Weight };
 
@@ -49,3 +65,23 @@ pub(crate) mod util;
 
 #[cfg(feature = "frozen")] mod frozen_weighted_list;
 #[cfg(feature = "frozen")] pub use frozen_weighted_list::{ FrozenWeightedList, FWList };
+
+#[cfg(feature = "serde")] pub mod repr;
+
+#[cfg(feature = "json")] mod interchange;
+#[cfg(feature = "json")] pub use interchange::{ INTERCHANGE_VERSION };
+
+#[cfg(feature = "mmap")] mod mapped_frozen_weighted_list;
+#[cfg(feature = "mmap")] pub use mapped_frozen_weighted_list::{ MappedFrozenWeightedList, MFWList, MappedWeight };
+
+#[cfg(feature = "csv")] mod csv_io;
+#[cfg(feature = "csv")] pub use csv_io::{ CsvColumn };
+
+#[cfg(feature = "loot-config")] mod loot_config;
+#[cfg(feature = "loot-config")] pub use loot_config::{ LootConfig, LootTable, LootEntry };
+
+#[cfg(feature = "registry")] mod table_registry;
+#[cfg(feature = "registry")] pub use table_registry::{ TableRegistry };
+
+#[cfg(feature = "minecraft")] mod minecraft;
+#[cfg(feature = "minecraft")] pub use minecraft::{ MinecraftLootTable, MinecraftPool, MinecraftEntry, MinecraftNumber, MinecraftWarning, MinecraftWarningKind };
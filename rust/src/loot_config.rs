@@ -0,0 +1,448 @@
+use std::collections::{ BTreeMap };
+use std::error::{ Error };
+use std::ops::{ RangeInclusive };
+
+use rand::prelude::*;
+use serde::{ Deserialize };
+
+use crate::*;
+use crate::errors::*;
+
+
+/// A registry of named loot tables, parsed and validated from a TOML or RON configuration.
+///
+/// # Format
+///
+/// Each table has optional `rolls`, `guaranteed` entries and weighted `entries`. Each entry is either an `item`, or a reference to another `table`, which is rolled in turn.
+///
+/// ```toml
+/// [tables.chest]
+/// rolls = "2-4"
+/// guaranteed = [{ item = "torch" }]
+/// entries = [
+///     { weight = 5, item = "stick" },
+///     { weight = 2.5, item = "iron" },
+///     { weight = 1, table = "treasure" },
+/// ]
+///
+/// [tables.treasure]
+/// entries = [{ weight = 1, item = "diamond" }]
+/// ```
+///
+/// - `rolls` is how many times to roll the weighted entries, either a count like `3` or an inclusive range like `"2-4"`. Defaults to `1`, or `0` if the table only has guaranteed entries.
+/// - `guaranteed` entries are given once every time the table is rolled, in addition to its rolls.
+/// - Weights default to `1`, and must be non-negative.
+///
+/// The same structure can be written in RON:
+///
+/// ```ron
+/// (tables: {
+///     "chest": (rolls: "2-4", entries: [(weight: 5, item: "stick"), (table: "treasure")]),
+///     "treasure": (entries: [(item: "diamond")]),
+/// })
+/// ```
+///
+/// # Usage
+///
+/// ```
+/// # use weighted_list::*;
+/// let config = LootConfig::<u32>::from_toml(r#"
+///     [tables.chest]
+///     rolls = "2-4"
+///     guaranteed = [{ item = "torch" }]
+///     entries = [{ weight = 3, item = "stick" }, { weight = 1, table = "treasure" }]
+///
+///     [tables.treasure]
+///     entries = [{ item = "diamond" }]
+/// "#).expect("config is valid");
+///
+/// let loot = config.roll("chest", &mut rand::rng())?;
+/// // could give ["torch", "stick", "diamond", "stick"]
+///
+/// assert_eq!( loot[0], "torch" );
+/// assert!( (3..=5).contains(&loot.len()) );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct LootConfig<W: Weight>
+{
+    tables: BTreeMap<String, LootTable<W>>,
+}
+
+/// A validated table in a [`LootConfig`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct LootTable<W: Weight>
+{
+    rolls: RangeInclusive<u32>,
+    guaranteed: Vec<LootEntry>,
+    entries: WeightedList<LootEntry, W>,
+}
+
+/// An entry in a [`LootTable`], which is either an item, or a reference to another table.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum LootEntry
+{
+    Item(String),
+    Table(String),
+}
+
+
+// == RAW CONFIG == //
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig
+{
+    #[serde(default)]
+    tables: BTreeMap<String, RawTable>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawTable
+{
+    rolls: Option<RawRolls>,
+    #[serde(default)]
+    guaranteed: Vec<RawEntry>,
+    #[serde(default)]
+    entries: Vec<RawEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawRolls
+{
+    Count(u32),
+    Range(String),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawEntry
+{
+    weight: Option<f64>,
+    item: Option<String>,
+    table: Option<String>,
+}
+
+
+// == CONSTRUCTORS == //
+impl<W: Weight> LootConfig<W>
+{
+    /// Parse and validate a configuration in TOML.
+    ///
+    /// See [`LootConfig`] for details of the format.
+    ///
+    /// # Errors
+    ///
+    /// Returns every [`LootConfigError`] found, including syntax errors, unknown references, cycles of references, invalid weights or rolls, and empty tables.
+    pub fn from_toml(source: &str) -> Result<Self, Vec<LootConfigError>>
+    {
+        let raw = toml::from_str::<RawConfig>(source).map_err(|err| {
+            let location = err.span()
+                .map(|span| _line_column_(source, span.start))
+                .unwrap_or_else(|| "document".to_string());
+
+            vec![LootConfigError { location, reason: err.message().to_string() }]
+        })?;
+
+        Self::_validate_(raw)
+    }
+
+    /// Parse and validate a configuration in RON.
+    ///
+    /// See [`LootConfig`] for details of the format, and [`::from_toml()`](Self::from_toml) for errors.
+    pub fn from_ron(source: &str) -> Result<Self, Vec<LootConfigError>>
+    {
+        let raw = ron::Options::default()
+            .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+            .from_str::<RawConfig>(source)
+            .map_err(|err| {
+                let location = format!("line {}, column {}", err.position.line, err.position.col);
+                vec![LootConfigError { location, reason: err.code.to_string() }]
+            })?;
+
+        Self::_validate_(raw)
+    }
+}
+
+// == ACCESSORS == //
+impl<W: Weight> LootConfig<W>
+{
+    /// Get the table named `name`, or `None` if there is no such table.
+    pub fn table(&self, name: &str) -> Option<&LootTable<W>>
+    {
+        self.tables.get(name)
+    }
+
+    /// Get an iterator over the names of the tables, in alphabetical order.
+    pub fn table_names(&self) -> impl Iterator<Item = &str>
+    {
+        self.tables.keys().map(String::as_str)
+    }
+}
+
+impl<W: Weight> LootTable<W>
+{
+    /// Get the range of how many times the weighted entries are rolled.
+    pub fn rolls(&self) -> &RangeInclusive<u32>
+    {
+        &self.rolls
+    }
+
+    /// Get the entries given every time the table is rolled.
+    pub fn guaranteed(&self) -> &[LootEntry]
+    {
+        &self.guaranteed
+    }
+
+    /// Get the weighted entries which are rolled.
+    pub fn entries(&self) -> &WeightedList<LootEntry, W>
+    {
+        &self.entries
+    }
+
+    /// Get a [`FrozenWeightedList`] of the weighted entries.
+    #[cfg(feature = "frozen")]
+    pub fn frozen_entries(&self) -> FrozenWeightedList<LootEntry, W>
+    {
+        FrozenWeightedList::init(self.entries.raw().map(|(weight, entry)| (weight, entry.clone())))
+    }
+}
+
+// == RANDOMISATION == //
+impl<W: Weight> LootConfig<W>
+{
+    /// Roll the table named `table_name`, returning the items given in order.
+    ///
+    /// Guaranteed entries are given first, followed by a random number of rolls of the weighted entries within the range of `rolls`. Entries referencing other tables roll those tables in turn.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`LootConfigError`] if there is no table named `table_name`.
+    pub fn roll<RNG>(&self, table_name: &str, rng: &mut RNG) -> Result<Vec<&str>, Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        let mut out = Vec::new();
+        self._roll_into_(table_name, rng, &mut out)?;
+
+        Ok(out)
+    }
+
+    fn _roll_into_<'c, RNG>(&'c self, table_name: &str, rng: &mut RNG, out: &mut Vec<&'c str>) -> Result<(), Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        let table = self.tables.get(table_name).ok_or_else(|| LootConfigError {
+            location: format!("tables.{table_name}"),
+            reason: "Unknown table".to_string(),
+        })?;
+
+        for entry in &table.guaranteed {
+            self._give_(entry, rng, out)?;
+        }
+
+        for _ in 0 .. rng.random_range(table.rolls.clone()) {
+            let entry = table.entries.select_random_value(rng)?;
+            self._give_(entry, rng, out)?;
+        }
+
+        Ok(())
+    }
+
+    fn _give_<'c, RNG>(&'c self, entry: &'c LootEntry, rng: &mut RNG, out: &mut Vec<&'c str>) -> Result<(), Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        match entry {
+            LootEntry::Item(item)   => out.push(item),
+            LootEntry::Table(table) => self._roll_into_(table, rng, out)?,
+        }
+
+        Ok(())
+    }
+}
+
+// == VALIDATION == //
+impl<W: Weight> LootConfig<W>
+{
+    fn _validate_(raw: RawConfig) -> Result<Self, Vec<LootConfigError>>
+    {
+        let mut errors = Vec::new();
+        let mut tables = BTreeMap::new();
+
+        for (name, table) in &raw.tables {
+            let mut error = |location: String, reason: String| errors.push(LootConfigError { location, reason });
+            let path = format!("tables.{name}");
+
+            let rolls = match &table.rolls {
+                None if table.entries.is_empty() => 0..=0,
+                None => 1..=1,
+                Some(rolls) => match _parse_rolls_(rolls) {
+                    Ok(rolls) => rolls,
+                    Err(reason) => {
+                        error(format!("{path}.rolls"), reason.to_string());
+                        0..=0
+                    },
+                },
+            };
+
+            let mut parse_entry = |location: String, entry: &RawEntry| {
+                let out = match (&entry.item, &entry.table) {
+                    (Some(item), None) => LootEntry::Item(item.clone()),
+                    (None, Some(table)) => {
+                        if !raw.tables.contains_key(table) {
+                            error(format!("{location}.table"), format!("Unknown table {table:?}"));
+                        }
+                        LootEntry::Table(table.clone())
+                    },
+                    _ => {
+                        error(location, "Expected exactly one of `item` or `table`".to_string());
+                        return None;
+                    },
+                };
+                Some(out)
+            };
+
+            let guaranteed = table.guaranteed.iter().enumerate()
+                .filter_map(|(i, entry)| parse_entry(format!("{path}.guaranteed[{i}]"), entry))
+                .collect::<Vec<_>>();
+
+            let entries = table.entries.iter().enumerate()
+                .filter_map(|(i, entry)| Some((i, parse_entry(format!("{path}.entries[{i}]"), entry)?)))
+                .collect::<Vec<_>>();
+
+            let mut error = |location: String, reason: String| errors.push(LootConfigError { location, reason });
+
+            for (i, entry) in table.guaranteed.iter().enumerate() {
+                if entry.weight.is_some() {
+                    error(format!("{path}.guaranteed[{i}].weight"), "Guaranteed entries cannot have a weight".to_string());
+                }
+            }
+
+            let mut list = WeightedList::new();
+
+            for (i, entry) in entries {
+                match _parse_weight_::<W>(table.entries[i].weight) {
+                    Ok(weight) => { list.push_new_item(weight, entry); },
+                    Err(reason) => error(format!("{path}.entries[{i}].weight"), reason),
+                }
+            }
+
+            if table.guaranteed.is_empty() && table.entries.is_empty() {
+                error(path, "Table is empty".to_string());
+            }
+            else if *rolls.end() > 0 && list.is_zero() && list.total_items() == table.entries.len() {
+                error(path, "Table has no entries with positive weight to roll".to_string());
+            }
+
+            tables.insert(name.clone(), LootTable { rolls, guaranteed, entries: list });
+        }
+
+        let out = Self { tables };
+        errors.extend(out._find_cycles_());
+
+        if errors.is_empty() { Ok(out) } else { Err(errors) }
+    }
+
+    /// Find each cycle of table references, reported at the first table in the cycle.
+    fn _find_cycles_(&self) -> Vec<LootConfigError>
+    {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State { Unvisited, Visiting, Done }
+
+        fn visit<'c, W: Weight>(
+            config: &'c LootConfig<W>,
+            name: &'c str,
+            states: &mut BTreeMap<&'c str, State>,
+            stack: &mut Vec<&'c str>,
+            errors: &mut Vec<LootConfigError>,
+        ) {
+            states.insert(name, State::Visiting);
+            stack.push(name);
+
+            let table = &config.tables[name];
+            let references = table.guaranteed.iter()
+                .chain(table.entries.values())
+                .filter_map(|entry| match entry {
+                    LootEntry::Table(table) if config.tables.contains_key(table) => Some(table.as_str()),
+                    _ => None,
+                });
+
+            for reference in references {
+                match states.get(reference).copied().unwrap_or(State::Unvisited) {
+                    State::Unvisited => visit(config, reference, states, stack, errors),
+                    State::Visiting => {
+                        let start = stack.iter().position(|&name| name == reference).expect("visiting tables are on the stack");
+                        let cycle = stack[start..].iter().chain([&reference]).copied().collect::<Vec<_>>();
+
+                        errors.push(LootConfigError {
+                            location: format!("tables.{reference}"),
+                            reason: format!("Cycle of table references {}", cycle.join(" -> ")),
+                        });
+                    },
+                    State::Done => {},
+                }
+            }
+
+            stack.pop();
+            states.insert(name, State::Done);
+        }
+
+        let mut states = BTreeMap::new();
+        let mut errors = Vec::new();
+
+        for name in self.tables.keys() {
+            if !states.contains_key(name.as_str()) {
+                visit(self, name, &mut states, &mut Vec::new(), &mut errors);
+            }
+        }
+
+        errors
+    }
+}
+
+fn _parse_rolls_(rolls: &RawRolls) -> Result<RangeInclusive<u32>, &'static str>
+{
+    let (min, max) = match rolls {
+        RawRolls::Count(count) => (*count, *count),
+        RawRolls::Range(range) => {
+            let parse = |part: &str| part.trim().parse::<u32>().map_err(|_| "Expected a count like `3` or a range like `2-4`");
+
+            match range.split_once('-') {
+                Some((min, max)) => (parse(min)?, parse(max)?),
+                None => { let count = parse(range)?; (count, count) },
+            }
+        },
+    };
+
+    if min > max {
+        return Err("Minimum rolls exceed maximum rolls");
+    }
+
+    Ok(min..=max)
+}
+
+fn _parse_weight_<W: Weight>(weight: Option<f64>) -> Result<W, String>
+{
+    let Some(weight) = weight else { return Ok(W::one()) };
+
+    if !weight.is_finite() {
+        return Err(format!("Weight must be finite, found {weight}"));
+    }
+    if weight < 0.0 {
+        return Err(format!("Weight must be non-negative, found {weight}"));
+    }
+    if util::is_integral::<W>() && weight.fract() != 0.0 {
+        return Err(format!("Weight must be an integer, found {weight}"));
+    }
+
+    util::try_cast::<f64, W>(weight).map_err(|err| err.to_string())
+}
+
+/// Get the line and column of byte `offset` in `source`, both starting from 1.
+fn _line_column_(source: &str, offset: usize) -> String
+{
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+
+    format!("line {line}, column {column}")
+}
@@ -0,0 +1,472 @@
+use std::error::{ Error };
+use std::fs::{ File };
+use std::io::{ self, Write };
+use std::marker::{ PhantomData };
+use std::path::{ Path };
+
+use memmap2::{ Mmap };
+use rand::prelude::*;
+
+use crate::*;
+use crate::errors::*;
+
+
+/// A shorthand for [`MappedFrozenWeightedList`].
+pub type MFWList<W> = MappedFrozenWeightedList<W>;
+
+
+/// Identifies a file in the binary format.
+const MAGIC: &[u8; 8] = b"WLISTBIN";
+
+/// The version of the binary format written by this crate.
+const VERSION: u32 = 1;
+
+/// The length of the header, after which the sections of the file begin.
+const HEADER_LEN: usize = 32;
+
+/// The size of each value offset.
+const OFFSET_LEN: usize = size_of::<u64>();
+
+
+/// A weight type with a fixed-size little-endian representation, which can be stored in the binary format of [`MappedFrozenWeightedList`].
+///
+/// This trait is sealed, and implemented for the primitive integer and float types up to 64 bits.
+pub trait MappedWeight: Weight + sealed::Sealed
+{
+    #[doc(hidden)]
+    const TAG: u8;
+
+    #[doc(hidden)]
+    const SIZE: usize;
+
+    #[doc(hidden)]
+    fn _from_le_bytes_(bytes: &[u8]) -> Self;
+
+    #[doc(hidden)]
+    fn _write_le_bytes_(self, out: &mut impl Write) -> io::Result<()>;
+}
+
+mod sealed
+{
+    pub trait Sealed {}
+}
+
+macro_rules! impl_mapped_weight {
+    ( $( $type: ty = $tag: expr ),* $(,)? ) => {
+        $(
+            impl sealed::Sealed for $type {}
+
+            impl MappedWeight for $type
+            {
+                const TAG: u8 = $tag;
+                const SIZE: usize = size_of::<$type>();
+
+                fn _from_le_bytes_(bytes: &[u8]) -> Self
+                {
+                    <$type>::from_le_bytes(bytes.try_into().expect("slice has the size of the weight type"))
+                }
+
+                fn _write_le_bytes_(self, out: &mut impl Write) -> io::Result<()>
+                {
+                    out.write_all(&self.to_le_bytes())
+                }
+            }
+        )*
+    };
+}
+
+impl_mapped_weight!(
+    u8 = 1, u16 = 2, u32 = 3, u64 = 4,
+    i8 = 5, i16 = 6, i32 = 7, i64 = 8,
+    f32 = 9, f64 = 10,
+);
+
+
+/// An immutable weighted list of byte-string values, read directly from a memory-mapped file in a compact binary format.
+///
+/// Unlike [`FrozenWeightedList`](crate::FrozenWeightedList), the items are never loaded into memory. Opening a list only checks its header, and lookups decode the cumulative weights and values they need from the mapped file, so even tables with tens of millions of items can be opened instantly. Lookup by weighted index and random selection are $O(\log n)$.
+///
+/// Files are written with [`WeightedList::write_binary()`](crate::WeightedList::write_binary), and must be read with the same weight type `W`.
+///
+/// # Usage
+///
+/// ```
+/// # use weighted_list::*;
+/// # let path = std::env::temp_dir().join("weighted-list-doctest-mapped.bin");
+/// let wl: WeightedList<&str, u32> = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+/// wl.write_binary(std::fs::File::create(&path)?)?;
+///
+/// // SAFETY: the file is not modified while it is mapped
+/// let mapped = unsafe { MappedFrozenWeightedList::<u32>::open(&path)? };
+///
+/// assert_eq!( mapped.len(), 10 );
+/// assert_eq!( mapped.get_value(4), Some(b"nova".as_slice()) );
+///
+/// let value = mapped.select_random_value(&mut rand::rng())?;
+/// println!("{}", String::from_utf8_lossy(value));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Format
+///
+/// All numbers are little-endian.
+///
+/// | offset | size | contents |
+/// | :----- | :--- | :------- |
+/// | `0` | `8` | `WLISTBIN` |
+/// | `8` | `4` | format version, currently `1` |
+/// | `12` | `1` | tag of the weight type |
+/// | `13` | `3` | reserved, all zero |
+/// | `16` | `8` | number of items `n` |
+/// | `24` | `8` | length of the value blob `b` |
+/// | `32` | `n × size_of::<W>()` | cumulative weights |
+/// | | `(n + 1) × 8` | offsets of each value in the blob, starting with `0` |
+/// | | `b` | value blob |
+pub struct MappedFrozenWeightedList<W: MappedWeight>
+{
+    mmap: Mmap,
+    len: usize,
+    blob_len: usize,
+    _weight: PhantomData<W>,
+}
+
+// == CONSTRUCTORS == //
+impl<W: MappedWeight> MappedFrozenWeightedList<W>
+{
+    /// Memory-map the file at `path` and open it as a list.
+    ///
+    /// Only the header and length of the file are checked, so this is $O(1)$. Items in a corrupted file may give incorrect results or panic when accessed.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while the list is alive, including by other processes. See [`memmap2::Mmap::map()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// - [`io::Error`] if the file cannot be opened or mapped.
+    /// - [`BinaryFormatError`] if the file is not in the binary format, or was written with a different weight type.
+    pub unsafe fn open(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>>
+    {
+        let file = File::open(path)?;
+
+        // SAFETY: upheld by the caller
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        Ok(Self::from_mmap(mmap)?)
+    }
+
+    /// Open an existing memory map as a list.
+    ///
+    /// See [`::open()`](Self::open) for details.
+    pub fn from_mmap(mmap: Mmap) -> Result<Self, BinaryFormatError>
+    {
+        let error = |reason: String| BinaryFormatError { reason };
+
+        if mmap.len() < HEADER_LEN || &mmap[0..8] != MAGIC {
+            return Err(error("Missing `WLISTBIN` header".to_string()));
+        }
+
+        let version = u32::from_le_bytes(mmap[8..12].try_into().unwrap());
+        if version != VERSION {
+            return Err(error(format!("Unsupported version {version}, expected {VERSION}")));
+        }
+
+        if mmap[12] != W::TAG {
+            return Err(error(format!("Weight type tag {} does not match {}", mmap[12], std::any::type_name::<W>())));
+        }
+
+        let len = u64::from_le_bytes(mmap[16..24].try_into().unwrap());
+        let blob_len = u64::from_le_bytes(mmap[24..32].try_into().unwrap());
+
+        let expected = usize::try_from(len).ok()
+            .zip(usize::try_from(blob_len).ok())
+            .and_then(|(len, blob_len)| {
+                len.checked_mul(W::SIZE + OFFSET_LEN)?
+                    .checked_add(HEADER_LEN + OFFSET_LEN)?
+                    .checked_add(blob_len)
+            });
+
+        if expected != Some(mmap.len()) {
+            return Err(error(format!("File has length {}, but the header describes {len} items with {blob_len} bytes of values", mmap.len())));
+        }
+
+        Ok(Self {
+            mmap,
+            len: len as usize,
+            blob_len: blob_len as usize,
+            _weight: PhantomData,
+        })
+    }
+}
+
+// == ACCESSORS == //
+impl<W: MappedWeight> MappedFrozenWeightedList<W>
+{
+    /// Get the cumulative weight of the item at unweighted index `idx`, or `None` if it is out of bounds.
+    pub fn cumulative_weight_at(&self, idx: usize) -> Option<W>
+    {
+        (idx < self.len).then(|| self._cumulative_weight_(idx))
+    }
+
+    /// Get the weight of the item at unweighted index `idx`, or `None` if it is out of bounds.
+    pub fn weight_at(&self, idx: usize) -> Option<W>
+    {
+        let cumulative = self.cumulative_weight_at(idx)?;
+
+        Some(
+            if idx == 0 { cumulative }
+            else { cumulative - self._cumulative_weight_(idx - 1) }
+        )
+    }
+
+    /// Get the value of the item at unweighted index `idx`, or `None` if it is out of bounds or its offsets are invalid.
+    pub fn value_at(&self, idx: usize) -> Option<&[u8]>
+    {
+        if idx >= self.len { return None }
+
+        let start = self._offset_(idx);
+        let end = self._offset_(idx + 1);
+
+        if start > end || end > self.blob_len { return None }
+
+        let blob = self._blob_start_();
+        self.mmap.get(blob + start .. blob + end)
+    }
+
+    /// Get an iterator over the weights of the items.
+    pub fn weights(&self) -> impl Iterator<Item = W>
+    {
+        (0 .. self.len).filter_map(|idx| self.weight_at(idx))
+    }
+
+    /// Get an iterator over the values of the items, skipping any with invalid offsets.
+    pub fn values(&self) -> impl Iterator<Item = &[u8]>
+    {
+        (0 .. self.len).filter_map(|idx| self.value_at(idx))
+    }
+
+    /// Get an iterator over the `(weight, value)` pairs of the items, skipping any with invalid offsets.
+    pub fn raw(&self) -> impl Iterator<Item = (W, &[u8])>
+    {
+        (0 .. self.len).filter_map(|idx| self.weight_at(idx).zip(self.value_at(idx)))
+    }
+}
+
+// == PROPERTIES == //
+impl<W: MappedWeight> MappedFrozenWeightedList<W>
+{
+    /// Get the total weight of the list, which is the cumulative weight of the last item.
+    pub fn len(&self) -> W
+    {
+        match self.len {
+            0   => W::zero(),
+            len => self._cumulative_weight_(len - 1),
+        }
+    }
+
+    /// Get the number of items in the list.
+    pub fn total_items(&self) -> usize
+    {
+        self.len
+    }
+
+    /// Check whether the list has no items.
+    pub fn is_empty(&self) -> bool
+    {
+        self.len == 0
+    }
+
+    /// Check whether the total weight of the list is zero.
+    pub fn is_zero(&self) -> bool
+    {
+        self.len() == W::zero()
+    }
+}
+
+// == INDEXING == //
+impl<W: MappedWeight> MappedFrozenWeightedList<W>
+{
+    /// Get the unweighted index of the item at `weighted_index`, or `None` if it is out of bounds. This is $O(\log n)$.
+    pub fn position_at(&self, weighted_index: W) -> Option<usize>
+    {
+        if weighted_index.partial_cmp(&W::zero()).is_none_or(|order| order.is_lt()) {
+            return None;
+        }
+
+        let idx = self._binary_unweight_index_(weighted_index);
+        (idx < self.len).then_some(idx)
+    }
+
+    /// Get the value of the item at `weighted_index`, or `None` if it is out of bounds. This is $O(\log n)$.
+    pub fn get_value(&self, weighted_index: W) -> Option<&[u8]>
+    {
+        self.value_at(self.position_at(weighted_index)?)
+    }
+}
+
+// == RANDOMISATION == //
+impl<W: MappedWeight> MappedFrozenWeightedList<W>
+{
+    /// Select the unweighted index of a random item, using weighted randomisation. This is $O(\log n)$.
+    pub fn select_random_index<RNG>(&self, rng: &mut RNG) -> Result<usize, Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        let total = self.len();
+
+        if total.partial_cmp(&W::zero()).is_none_or(|order| order.is_le()) {
+            Err(Box::new(EmptyWeightedList { reason: "Cannot select a random item from a `MappedFrozenWeightedList` with no positive weight" }))?
+        }
+
+        let scaled = util::try_cast::<W, f64>(total)? * rng.random::<f64>();
+        let weighted_index = util::try_cast::<f64, W>(
+            if util::is_integral::<W>() { scaled.floor() } else { scaled }
+        )?;
+
+        // fall back to the last item with positive weight in case of float rounding
+        Ok(
+            self.position_at(weighted_index)
+                .unwrap_or_else(|| self._partition_point_(|cumulative| cumulative < total))
+        )
+    }
+
+    /// Select the value of a random item, using weighted randomisation. This is $O(\log n)$.
+    pub fn select_random_value<RNG>(&self, rng: &mut RNG) -> Result<&[u8], Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        let idx = self.select_random_index(rng)?;
+
+        Ok(
+            self.value_at(idx)
+                .ok_or_else(|| BinaryFormatError { reason: format!("Item {idx} has invalid value offsets") })?
+        )
+    }
+}
+
+// == WRITING == //
+/// Methods for writing the binary format of [`MappedFrozenWeightedList`].
+impl<V, W: MappedWeight> WeightedList<V,W>
+    where V: AsRef<[u8]>
+{
+    /// Write the list in the binary format read by [`MappedFrozenWeightedList`].
+    ///
+    /// # Errors
+    ///
+    /// - [`BinaryFormatError`] if any weights are negative.
+    /// - [`WeightOverflow`] if the cumulative weights overflow `W`.
+    /// - [`io::Error`] if writing fails.
+    pub fn write_binary(&self, writer: impl Write) -> Result<(), Box<dyn Error>>
+    {
+        _write_binary_(|| self.raw(), writer)
+    }
+}
+
+/// Methods for writing the binary format of [`MappedFrozenWeightedList`].
+#[cfg(feature = "frozen")]
+impl<V, W: MappedWeight> FrozenWeightedList<V,W>
+    where V: AsRef<[u8]>
+{
+    /// Write the list in the binary format read by [`MappedFrozenWeightedList`].
+    ///
+    /// See [`WeightedList::write_binary()`] for details.
+    pub fn write_binary(&self, writer: impl Write) -> Result<(), Box<dyn Error>>
+    {
+        _write_binary_(|| self.raw(), writer)
+    }
+}
+
+/// Write the items produced by `items` in the binary format, iterating over them several times.
+fn _write_binary_<'v, V, W, I>(items: impl Fn() -> I, writer: impl Write) -> Result<(), Box<dyn Error>>
+    where
+        V: AsRef<[u8]> + 'v,
+        W: MappedWeight,
+        I: Iterator<Item = (W, &'v V)>,
+{
+    let mut writer = io::BufWriter::new(writer);
+
+    let mut len: u64 = 0;
+    let mut blob_len: u64 = 0;
+    let mut cumulative = W::zero();
+
+    // validate everything before writing anything, so that failing does not leave a truncated file
+    for (i, (weight, value)) in items().enumerate() {
+        if weight.partial_cmp(&W::zero()).is_none_or(|order| order.is_lt()) {
+            Err(BinaryFormatError { reason: format!("Item {i} has weight {weight:?}, but weights must be non-negative") })?
+        }
+
+        cumulative = util::checked_add(cumulative, weight)?;
+        len += 1;
+        blob_len += value.as_ref().len() as u64;
+    }
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&[W::TAG, 0, 0, 0])?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(&blob_len.to_le_bytes())?;
+
+    let mut cumulative = W::zero();
+    for (weight, _) in items() {
+        cumulative += weight;
+        cumulative._write_le_bytes_(&mut writer)?;
+    }
+
+    let mut offset: u64 = 0;
+    writer.write_all(&offset.to_le_bytes())?;
+    for (_, value) in items() {
+        offset += value.as_ref().len() as u64;
+        writer.write_all(&offset.to_le_bytes())?;
+    }
+
+    for (_, value) in items() {
+        writer.write_all(value.as_ref())?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+// == INTERNAL == //
+impl<W: MappedWeight> MappedFrozenWeightedList<W>
+{
+    fn _cumulative_weight_(&self, idx: usize) -> W
+    {
+        let start = HEADER_LEN + idx * W::SIZE;
+        W::_from_le_bytes_(&self.mmap[start .. start + W::SIZE])
+    }
+
+    fn _offset_(&self, idx: usize) -> usize
+    {
+        let start = HEADER_LEN + self.len * W::SIZE + idx * OFFSET_LEN;
+        u64::from_le_bytes(self.mmap[start .. start + OFFSET_LEN].try_into().unwrap()) as usize
+    }
+
+    fn _blob_start_(&self) -> usize
+    {
+        HEADER_LEN + self.len * W::SIZE + (self.len + 1) * OFFSET_LEN
+    }
+
+    /// Find the first item whose cumulative weight exceeds `weighted_index`, which is `self.len` if there is none.
+    fn _binary_unweight_index_(&self, weighted_index: W) -> usize
+    {
+        self._partition_point_(|cumulative| cumulative <= weighted_index)
+    }
+
+    /// Find the first index for which `pred` is false, given that it is true for a prefix of the cumulative weights.
+    fn _partition_point_(&self, mut pred: impl FnMut(W) -> bool) -> usize
+    {
+        let mut left_idx = 0;
+        let mut right_idx = self.len;
+
+        while left_idx < right_idx {
+            let pivot_idx = left_idx.midpoint(right_idx);
+
+            if pred(self._cumulative_weight_(pivot_idx)) {
+                left_idx = pivot_idx + 1;
+            } else {
+                right_idx = pivot_idx;
+            }
+        }
+
+        left_idx
+    }
+}
@@ -0,0 +1,394 @@
+use std::collections::{ HashMap, VecDeque };
+use std::error::{ Error };
+use std::hash::{ Hash };
+
+use rand::prelude::*;
+
+use crate::*;
+use crate::errors::*;
+
+
+/// A Markov chain over states of type `S`, storing a [`WeightedList`] of transitions for each context of preceding states.
+///
+/// The `order` of the chain is how many preceding states determine the next one. An order of `1` is a standard Markov chain, while higher orders give n-gram models.
+///
+/// A context with no transitions is terminal, so walks stop when they reach it.
+///
+/// # Usage
+///
+/// ```
+/// # use weighted_list::*;
+/// let mut chain = MarkovChain::<char, u32>::new(1);
+/// chain.train("abracadabra".chars());
+///
+/// assert_eq!( chain.transitions(&['a']), Some(&wlist![(2, 'b'), (1, 'c'), (1, 'd')]) );
+///
+/// let word = chain.walk(&mut rand::rng(), ['a']).take(10).collect::<String>();
+/// assert!( word.len() <= 10 );
+/// ```
+#[derive(Clone, Debug)]
+pub struct MarkovChain<S, W: Weight>
+{
+    order: usize,
+    indices: HashMap<Vec<S>, usize>,
+    transitions: Vec<(Vec<S>, WeightedList<S,W>)>,
+}
+
+// == CONSTRUCTORS == //
+impl<S, W: Weight> MarkovChain<S,W>
+    where S: Clone + Hash + Eq
+{
+    /// Construct an empty [`MarkovChain`] where the next state depends on the previous `order` states.
+    pub fn new(order: usize) -> Self
+    {
+        Self {
+            order,
+            indices: HashMap::new(),
+            transitions: Vec::new(),
+        }
+    }
+
+    /// Train the chain on a `sequence` of states, counting each transition from the preceding `order` states to the next state.
+    ///
+    /// The final context of the sequence gains no transitions, so it is terminal unless other training gives it any.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut chain = MarkovChain::<&str, u32>::new(2);
+    /// chain.train("the cat sat on the cat mat".split(' '));
+    ///
+    /// assert_eq!( chain.transitions(&["the", "cat"]), Some(&wlist![(1, "sat"), (1, "mat")]) );
+    /// ```
+    pub fn train(&mut self, sequence: impl IntoIterator<Item = S>) -> &mut Self
+    {
+        let mut context = VecDeque::with_capacity(self.order + 1);
+
+        for state in sequence {
+            if context.len() == self.order {
+                self.add_transition(context.iter().cloned().collect(), W::one(), state.clone());
+            }
+
+            context.push_back(state);
+
+            if context.len() > self.order {
+                context.pop_front();
+            }
+        }
+
+        self
+    }
+
+    /// Add a transition from `context` to the state `next` with `weight`, merging it with any existing transition.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `context` is not the order of the chain.
+    pub fn add_transition(&mut self, context: Vec<S>, weight: W, next: S) -> &mut Self
+    {
+        assert_eq!( context.len(), self.order, "Context length must match the order of the chain" );
+
+        let idx = match self.indices.get(&context) {
+            Some(&idx) => idx,
+            None => {
+                let idx = self.transitions.len();
+                self.indices.insert(context.clone(), idx);
+                self.transitions.push((context, WeightedList::new()));
+                idx
+            }
+        };
+
+        self.transitions[idx].1.merge_new_item(weight, next);
+        self
+    }
+}
+
+// == ACCESSORS == //
+impl<S, W: Weight> MarkovChain<S,W>
+    where S: Clone + Hash + Eq
+{
+    /// How many preceding states determine the next state.
+    pub fn order(&self) -> usize
+    {
+        self.order
+    }
+
+    /// Get the transitions from `context`, or `None` if the chain has never seen it.
+    pub fn transitions(&self, context: &[S]) -> Option<&WeightedList<S,W>>
+    {
+        self.indices.get(context).map(|&idx| &self.transitions[idx].1)
+    }
+
+    /// Get an iterator over each context and its transitions, in the order the contexts were first seen.
+    pub fn contexts(&self) -> impl Iterator<Item = (&[S], &WeightedList<S,W>)>
+    {
+        self.transitions.iter().map(|(context, list)| (context.as_slice(), list))
+    }
+
+    /// Is `context` terminal, with no transitions out of it?
+    pub fn is_terminal(&self, context: &[S]) -> bool
+    {
+        self.transitions(context).is_none_or(WeightedList::is_zero)
+    }
+}
+
+// == GENERATION == //
+impl<S, W: Weight> MarkovChain<S,W>
+    where S: Clone + Hash + Eq
+{
+    /// Select a random next state from the last `order` states of `history`, using weighted randomisation.
+    ///
+    /// Returns `None` if the context is terminal, or `history` is shorter than the order of the chain.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut chain = MarkovChain::<&str, u32>::new(1);
+    /// chain.train(["sup", "nova", "shard"]);
+    ///
+    /// let mut rng = rand::rng();
+    /// assert_eq!( chain.next_state(&mut rng, &["sup"]), Some(&"nova") );
+    /// assert_eq!( chain.next_state(&mut rng, &["sup", "nova"]), Some(&"shard") );
+    /// assert_eq!( chain.next_state(&mut rng, &["shard"]), None );
+    /// ```
+    pub fn next_state<RNG>(&self, rng: &mut RNG, history: &[S]) -> Option<&S>
+        where RNG: Rng + ?Sized
+    {
+        let start = history.len().checked_sub(self.order)?;
+        let list = self.transitions(&history[start..])?;
+
+        list.select_random_value(rng).ok()
+    }
+
+    /// Get an iterator walking randomly through the chain from the states in `start`, yielding each new state it reaches. The iterator ends upon reaching a terminal context.
+    ///
+    /// The last `order` states of `start` form the initial context, and are not yielded.
+    pub fn walk<'c, 'r, RNG>(&'c self,
+        rng: &'r mut RNG,
+        start: impl IntoIterator<Item = S>,
+    ) -> MarkovWalk<'c, 'r, S, W, RNG>
+        where RNG: Rng + ?Sized
+    {
+        let mut context = start.into_iter().collect::<VecDeque<_>>();
+        let excess = context.len().saturating_sub(self.order);
+        context.drain(..excess);
+
+        MarkovWalk { chain: self, rng, context }
+    }
+}
+
+// == ANALYSIS == //
+impl<S, W: Weight> MarkovChain<S,W>
+    where S: Clone + Hash + Eq
+{
+    /// Compute the stationary distribution of the chain, the long-run proportion of time spent in each state. Weights are normalised to sum to `1.0`, and states are ordered by when they were first seen.
+    ///
+    /// This iterates from a uniform distribution over contexts until it converges. Terminal contexts are absorbing, so with any reachable terminal contexts, the distribution concentrates on them.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut chain = MarkovChain::<&str, u32>::new(1);
+    /// chain
+    ///     .add_transition(vec!["sunny"], 9, "sunny")
+    ///     .add_transition(vec!["sunny"], 1, "rainy")
+    ///     .add_transition(vec!["rainy"], 1, "sunny")
+    ///     .add_transition(vec!["rainy"], 1, "rainy");
+    ///
+    /// let stationary = chain.stationary_distribution()?;
+    /// let sunny = stationary.weights().next().unwrap();
+    ///
+    /// assert!( (sunny - 5.0 / 6.0).abs() < 1e-9 );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NoConvergence`] if the distribution has not converged after 100,000 iterations, which can happen for chains that rarely move between some of their states.
+    ///
+    /// # Notes
+    ///
+    /// - For an order of `0`, the next state does not depend on any previous states, so this is the normalised transitions.
+    pub fn stationary_distribution(&self) -> Result<WeightedList<S, f64>, Box<dyn Error>>
+    {
+        const MAX_ITERATIONS: usize = 100_000;
+        const TOLERANCE: f64 = 1e-12;
+
+        if self.order == 0 {
+            return match self.transitions(&[]) {
+                Some(list) if !list.is_zero() => Ok(list.normalised()?),
+                _ => Ok(WeightedList::new()),
+            };
+        }
+
+        // discover every reachable context, including terminal ones
+        let mut contexts = self.transitions.iter()
+            .map(|(context, _)| context.clone())
+            .collect::<Vec<_>>();
+        let mut indices = self.indices.clone();
+        let mut rows = Vec::<Vec<(usize, f64)>>::new();
+
+        let mut i = 0;
+        while i < contexts.len() {
+            let mut row = Vec::new();
+
+            if let Some(list) = self.transitions(&contexts[i]) && !list.is_zero() {
+                let total = util::try_cast::<W, f64>(list.len())?;
+
+                for item in list {
+                    let next = contexts[i][1..].iter()
+                        .chain([&item.value])
+                        .cloned()
+                        .collect::<Vec<_>>();
+
+                    let idx = *indices.entry(next.clone()).or_insert_with(|| {
+                        contexts.push(next);
+                        contexts.len() - 1
+                    });
+
+                    row.push((idx, util::try_cast::<W, f64>(item.weight)? / total));
+                }
+            }
+
+            rows.push(row);
+            i += 1;
+        }
+
+        let n = contexts.len();
+        let mut dist = vec![1.0 / n as f64; n];
+        let mut converged = n == 0;
+
+        for _ in 0..MAX_ITERATIONS {
+            // half-lazy steps converge even for periodic chains, with the same stationary distribution
+            let mut next = dist.iter().map(|p| p / 2.0).collect::<Vec<_>>();
+
+            for (from, row) in rows.iter().enumerate() {
+                if row.is_empty() {
+                    next[from] += dist[from] / 2.0;
+                }
+
+                for &(to, p) in row {
+                    next[to] += dist[from] * p / 2.0;
+                }
+            }
+
+            let change = dist.iter().zip(&next).map(|(p, q)| (p - q).abs()).sum::<f64>();
+            dist = next;
+
+            if change < TOLERANCE {
+                converged = true;
+                break
+            }
+        }
+
+        if !converged {
+            return Err(Box::new(NoConvergence { iterations: MAX_ITERATIONS }));
+        }
+
+        let mut states = Vec::<(f64, S)>::new();
+        let mut seen = HashMap::<&S, usize>::new();
+
+        for (context, p) in contexts.iter().zip(dist) {
+            let state = context.last().expect("contexts are nonempty for orders above 0");
+
+            match seen.get(state) {
+                Some(&idx) => states[idx].0 += p,
+                None => {
+                    seen.insert(state, states.len());
+                    states.push((p, state.clone()));
+                }
+            }
+        }
+
+        Ok(states.into_iter().collect())
+    }
+}
+
+
+/// An iterator walking randomly through a [`MarkovChain`], created by [`MarkovChain::walk()`].
+pub struct MarkovWalk<'c, 'r, S, W: Weight, RNG: ?Sized>
+{
+    chain: &'c MarkovChain<S,W>,
+    rng: &'r mut RNG,
+    context: VecDeque<S>,
+}
+
+impl<S, W: Weight, RNG> Iterator for MarkovWalk<'_, '_, S, W, RNG>
+    where
+        S: Clone + Hash + Eq,
+        RNG: Rng + ?Sized,
+{
+    type Item = S;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        let next = self.chain.next_state(self.rng, self.context.make_contiguous())?.clone();
+
+        self.context.push_back(next.clone());
+
+        if self.context.len() > self.chain.order {
+            self.context.pop_front();
+        }
+
+        Some(next)
+    }
+}
+
+// == SERDE == //
+#[cfg(feature = "serde")]
+impl<S, W> serde::Serialize for MarkovChain<S,W>
+    where
+        S: serde::Serialize,
+        W: Weight + serde::Serialize,
+{
+    /// Serialises as a struct of the `order` and a sequence of `[context, transitions]` pairs.
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("MarkovChain", 2)?;
+        state.serialize_field("order", &self.order)?;
+        state.serialize_field("transitions", &self.transitions)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, S, W> serde::Deserialize<'de> for MarkovChain<S,W>
+    where
+        S: serde::Deserialize<'de> + Clone + Hash + Eq,
+        W: Weight + serde::Deserialize<'de>,
+{
+    /// Contexts must match the order of the chain. Duplicate contexts and transitions are merged.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        struct Raw<S, W: Weight>
+        {
+            order: usize,
+            transitions: Vec<(Vec<S>, WeightedList<S,W>)>,
+        }
+
+        let raw = Raw::<S,W>::deserialize(deserializer)?;
+        let mut out = Self::new(raw.order);
+
+        for (context, list) in raw.transitions {
+            if context.len() != raw.order {
+                return Err(D::Error::custom(format!(
+                    "context has {} states, but the chain has order {}", context.len(), raw.order
+                )));
+            }
+
+            for item in list {
+                out.add_transition(context.clone(), item.weight, item.value);
+            }
+        }
+
+        Ok(out)
+    }
+}
@@ -0,0 +1,448 @@
+//! An importer for loot tables in the JSON format used by Minecraft and its community content.
+
+use std::error::{ Error };
+use std::fmt::{ self, Display };
+use std::io;
+
+use rand::prelude::*;
+use serde_json::{ Map, Value };
+
+use crate::*;
+use crate::errors::*;
+
+
+/// A loot table imported from the Minecraft loot table JSON format.
+///
+/// Each pool is rolled independently, and becomes its own [`WeightedList`] of entries. The quality of each entry adjusts its weight by the luck of the roll, so that its weight is `floor(weight + quality * luck)`, but never negative.
+///
+/// Functions, conditions and other features which cannot be represented are not applied, but are reported as [`MinecraftWarning`]s.
+///
+/// # Usage
+///
+/// ```
+/// # use weighted_list::*;
+/// let json = r#"{
+///     "type": "minecraft:chest",
+///     "pools": [{
+///         "rolls": { "type": "minecraft:uniform", "min": 1, "max": 3 },
+///         "entries": [
+///             { "type": "minecraft:item", "name": "minecraft:stick", "weight": 10 },
+///             { "type": "minecraft:item", "name": "minecraft:diamond", "weight": 1, "quality": 2 },
+///             { "type": "minecraft:empty", "weight": 5 }
+///         ]
+///     }]
+/// }"#;
+///
+/// let table = MinecraftLootTable::read_json(json.as_bytes())?;
+/// let pool = &table.pools()[0];
+///
+/// assert_eq!( pool.weighted_list(0.0).weights().collect::<Vec<_>>(), vec![10, 1, 5] );
+/// assert_eq!( pool.weighted_list(1.5).weights().collect::<Vec<_>>(), vec![10, 4, 5] );
+///
+/// let loot = table.roll(0.0, &mut rand::rng())?;
+/// assert!( loot.len() <= 3 );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct MinecraftLootTable
+{
+    pools: Vec<MinecraftPool>,
+    warnings: Vec<MinecraftWarning>,
+}
+
+/// A pool of a [`MinecraftLootTable`], which is rolled independently of the other pools.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MinecraftPool
+{
+    rolls: MinecraftNumber,
+    bonus_rolls: MinecraftNumber,
+    entries: Vec<(MinecraftEntry, u32, i32)>,
+}
+
+/// An entry of a [`MinecraftPool`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum MinecraftEntry
+{
+    /// An item, such as `minecraft:stick`.
+    Item(String),
+    /// Any item in a tag, such as `minecraft:logs`.
+    Tag(String),
+    /// A reference to another loot table, such as `minecraft:chests/simple_dungeon`.
+    Table(String),
+    /// Nothing.
+    Empty,
+}
+
+/// A number provider for the rolls of a [`MinecraftPool`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MinecraftNumber
+{
+    Constant(f64),
+    Uniform { min: f64, max: f64 },
+    Binomial { n: u32, p: f64 },
+}
+
+/// A feature of a Minecraft loot table which was not imported.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MinecraftWarning
+{
+    location: String,
+    kind: MinecraftWarningKind,
+    name: String,
+}
+
+/// The kind of feature a [`MinecraftWarning`] is about.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MinecraftWarningKind
+{
+    /// A function, which was not applied.
+    Function,
+    /// A condition, which was ignored, so its entry or pool is always included.
+    Condition,
+    /// An entry type such as `minecraft:alternatives`, which was left out of its pool.
+    Entry,
+    /// A number provider such as `minecraft:score`, which was replaced by a constant `1`.
+    Number,
+}
+
+
+// == IMPORTING == //
+impl MinecraftLootTable
+{
+    /// Import a loot table from the Minecraft loot table JSON format.
+    ///
+    /// - Entries of type `minecraft:item`, `minecraft:tag`, `minecraft:loot_table` and `minecraft:empty` are imported, with their `weight` and `quality`.
+    /// - `rolls` and `bonus_rolls` can be constant, `minecraft:uniform` or `minecraft:binomial`.
+    /// - Functions, conditions, composite entries such as `minecraft:alternatives`, and other number providers are reported in [`::warnings()`](Self::warnings).
+    ///
+    /// # Errors
+    ///
+    /// - [`MinecraftImportError`] if the JSON is not a loot table, such as if `pools` is not an array, or an entry is missing its `name`.
+    /// - [`serde_json::Error`] if the document is not valid JSON.
+    pub fn read_json<R>(reader: R) -> Result<Self, Box<dyn Error>>
+        where R: io::Read
+    {
+        let doc: Value = serde_json::from_reader(reader)?;
+        let mut warnings = Vec::new();
+
+        let doc = _object_(&doc, "table")?;
+        _warn_unsupported_(doc, "", &mut warnings)?;
+
+        let pools = match doc.get("pools") {
+            None => Vec::new(),
+            Some(pools) => _array_(pools, "pools")?.iter().enumerate()
+                .map(|(i, pool)| _read_pool_(pool, &format!("pools[{i}]"), &mut warnings))
+                .collect::<Result<_,_>>()?,
+        };
+
+        Ok(Self { pools, warnings })
+    }
+}
+
+fn _read_pool_(pool: &Value, location: &str, warnings: &mut Vec<MinecraftWarning>) -> Result<MinecraftPool, MinecraftImportError>
+{
+    let pool = _object_(pool, location)?;
+    _warn_unsupported_(pool, location, warnings)?;
+
+    let rolls = match pool.get("rolls") {
+        Some(rolls) => _read_number_(rolls, &format!("{location}.rolls"), warnings)?,
+        None => Err(_error_(location, "Missing `rolls`"))?,
+    };
+
+    let bonus_rolls = match pool.get("bonus_rolls") {
+        Some(bonus_rolls) => _read_number_(bonus_rolls, &format!("{location}.bonus_rolls"), warnings)?,
+        None => MinecraftNumber::Constant(0.0),
+    };
+
+    let mut entries = Vec::new();
+
+    if let Some(raw) = pool.get("entries") {
+        for (i, entry) in _array_(raw, &format!("{location}.entries"))?.iter().enumerate() {
+            let location = format!("{location}.entries[{i}]");
+            let entry = _object_(entry, &location)?;
+            _warn_unsupported_(entry, &location, warnings)?;
+
+            let name = |keys: &[&str]| {
+                keys.iter()
+                    .find_map(|key| entry.get(*key))
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+                    .ok_or_else(|| _error_(&location, &format!("Missing `{}`", keys[0])))
+            };
+
+            let kind = entry.get("type").and_then(Value::as_str).unwrap_or_default();
+
+            let value = match kind.strip_prefix("minecraft:").unwrap_or(kind) {
+                "item"       => MinecraftEntry::Item(name(&["name"])?),
+                "tag"        => MinecraftEntry::Tag(name(&["name"])?),
+                "loot_table" => MinecraftEntry::Table(name(&["value", "name"])?),
+                "empty"      => MinecraftEntry::Empty,
+                _ => {
+                    warnings.push(MinecraftWarning { location, kind: MinecraftWarningKind::Entry, name: kind.to_string() });
+                    continue;
+                },
+            };
+
+            let weight = match entry.get("weight") {
+                None => 1,
+                Some(weight) => weight.as_u64()
+                    .and_then(|weight| u32::try_from(weight).ok())
+                    .ok_or_else(|| _error_(&location, &format!("Invalid weight {weight}, expected a non-negative integer")))?,
+            };
+
+            let quality = match entry.get("quality") {
+                None => 0,
+                Some(quality) => quality.as_i64()
+                    .and_then(|quality| i32::try_from(quality).ok())
+                    .ok_or_else(|| _error_(&location, &format!("Invalid quality {quality}, expected an integer")))?,
+            };
+
+            entries.push((value, weight, quality));
+        }
+    }
+
+    Ok(MinecraftPool { rolls, bonus_rolls, entries })
+}
+
+fn _read_number_(number: &Value, location: &str, warnings: &mut Vec<MinecraftWarning>) -> Result<MinecraftNumber, MinecraftImportError>
+{
+    if let Some(value) = number.as_f64() {
+        return Ok(MinecraftNumber::Constant(value));
+    }
+
+    let object = _object_(number, location)?;
+    let field = |key: &str| {
+        object.get(key)
+            .and_then(Value::as_f64)
+            .ok_or_else(|| _error_(location, &format!("Missing or non-constant `{key}`")))
+    };
+
+    let kind = object.get("type").and_then(Value::as_str)
+        .unwrap_or(if object.contains_key("value") { "constant" } else { "uniform" });
+
+    let out = match kind.strip_prefix("minecraft:").unwrap_or(kind) {
+        "constant" => MinecraftNumber::Constant(field("value")?),
+        "uniform"  => MinecraftNumber::Uniform { min: field("min")?, max: field("max")? },
+        "binomial" => MinecraftNumber::Binomial {
+            n: u32::try_from(field("n")? as i64).map_err(|_| _error_(location, "Invalid `n`, expected a non-negative integer"))?,
+            p: field("p")?,
+        },
+        _ => {
+            warnings.push(MinecraftWarning { location: location.to_string(), kind: MinecraftWarningKind::Number, name: kind.to_string() });
+            MinecraftNumber::Constant(1.0)
+        },
+    };
+
+    Ok(out)
+}
+
+/// Add a warning for each function and condition of `object`.
+fn _warn_unsupported_(object: &Map<String, Value>, location: &str, warnings: &mut Vec<MinecraftWarning>) -> Result<(), MinecraftImportError>
+{
+    let prefix = if location.is_empty() { String::new() } else { format!("{location}.") };
+
+    for (key, kind) in [("functions", MinecraftWarningKind::Function), ("conditions", MinecraftWarningKind::Condition)] {
+        let Some(items) = object.get(key) else { continue };
+
+        for (i, item) in _array_(items, &format!("{prefix}{key}"))?.iter().enumerate() {
+            let field = if kind == MinecraftWarningKind::Function { "function" } else { "condition" };
+            let name = item.get(field).and_then(Value::as_str).unwrap_or("unknown");
+
+            warnings.push(MinecraftWarning { location: format!("{prefix}{key}[{i}]"), kind, name: name.to_string() });
+        }
+    }
+
+    Ok(())
+}
+
+fn _object_<'v>(value: &'v Value, location: &str) -> Result<&'v Map<String, Value>, MinecraftImportError>
+{
+    value.as_object().ok_or_else(|| _error_(location, "Expected an object"))
+}
+
+fn _array_<'v>(value: &'v Value, location: &str) -> Result<&'v Vec<Value>, MinecraftImportError>
+{
+    value.as_array().ok_or_else(|| _error_(location, "Expected an array"))
+}
+
+fn _error_(location: &str, reason: &str) -> MinecraftImportError
+{
+    MinecraftImportError { location: location.to_string(), reason: reason.to_string() }
+}
+
+
+// == ACCESSORS == //
+impl MinecraftLootTable
+{
+    /// Get the pools of the table.
+    pub fn pools(&self) -> &[MinecraftPool]
+    {
+        &self.pools
+    }
+
+    /// Get the warnings for each feature of the JSON which was not imported.
+    pub fn warnings(&self) -> &[MinecraftWarning]
+    {
+        &self.warnings
+    }
+}
+
+impl MinecraftPool
+{
+    /// Get the number of rolls of the pool.
+    pub fn rolls(&self) -> MinecraftNumber
+    {
+        self.rolls
+    }
+
+    /// Get the number of extra rolls of the pool per point of luck.
+    pub fn bonus_rolls(&self) -> MinecraftNumber
+    {
+        self.bonus_rolls
+    }
+
+    /// Get an iterator over each entry of the pool, with its base weight and quality.
+    pub fn entries(&self) -> impl Iterator<Item = (&MinecraftEntry, u32, i32)>
+    {
+        self.entries.iter().map(|(entry, weight, quality)| (entry, *weight, *quality))
+    }
+
+    /// Get a [`WeightedList`] of the entries of the pool, with their weights adjusted by `luck`.
+    ///
+    /// Each weight is `floor(weight + quality * luck)`, but never negative.
+    pub fn weighted_list(&self, luck: f64) -> WeightedList<MinecraftEntry, u32>
+    {
+        self.entries.iter()
+            .map(|(entry, weight, quality)| (_adjust_weight_(*weight, *quality, luck), entry.clone()))
+            .collect()
+    }
+}
+
+fn _adjust_weight_(weight: u32, quality: i32, luck: f64) -> u32
+{
+    (weight as f64 + quality as f64 * luck).floor().max(0.0) as u32
+}
+
+impl MinecraftWarning
+{
+    /// Get the location of the feature in the JSON, such as `pools[0].entries[2].functions[0]`.
+    pub fn location(&self) -> &str
+    {
+        &self.location
+    }
+
+    /// Get the kind of feature.
+    pub fn kind(&self) -> MinecraftWarningKind
+    {
+        self.kind
+    }
+
+    /// Get the name of the feature, such as `minecraft:set_count`.
+    pub fn name(&self) -> &str
+    {
+        &self.name
+    }
+}
+
+impl Display for MinecraftWarning
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let kind = match self.kind {
+            MinecraftWarningKind::Function  => "function",
+            MinecraftWarningKind::Condition => "condition",
+            MinecraftWarningKind::Entry     => "entry type",
+            MinecraftWarningKind::Number    => "number provider",
+        };
+
+        write!(f, "Unsupported {kind} {:?} at {}", self.name, self.location)
+    }
+}
+
+
+// == RANDOMISATION == //
+impl MinecraftLootTable
+{
+    /// Roll each pool of the table in order, returning the entries selected.
+    ///
+    /// Empty entries are left out, and referenced tables are returned as [`MinecraftEntry::Table`] without being rolled.
+    pub fn roll<RNG>(&self, luck: f64, rng: &mut RNG) -> Result<Vec<&MinecraftEntry>, Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        let mut out = Vec::new();
+
+        for pool in &self.pools {
+            out.extend(pool.roll(luck, rng)?);
+        }
+
+        Ok(out)
+    }
+}
+
+impl MinecraftPool
+{
+    /// Get a random number of times to roll the pool, including bonus rolls for `luck`.
+    pub fn roll_count<RNG>(&self, luck: f64, rng: &mut RNG) -> u32
+        where RNG: Rng + ?Sized
+    {
+        let rolls = self.rolls.sample_int(rng);
+        let bonus = (self.bonus_rolls.sample(rng) * luck).floor() as i64;
+
+        (rolls + bonus).clamp(0, u32::MAX as i64) as u32
+    }
+
+    /// Roll the pool, returning the entries selected.
+    ///
+    /// Empty entries are left out. If every weight is zero after adjusting for `luck`, nothing is selected.
+    pub fn roll<RNG>(&self, luck: f64, rng: &mut RNG) -> Result<Vec<&MinecraftEntry>, Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        let list = self.entries.iter()
+            .map(|(entry, weight, quality)| (_adjust_weight_(*weight, *quality, luck), entry))
+            .collect::<WeightedList<_,_>>();
+
+        let mut out = Vec::new();
+
+        if list.is_zero() {
+            return Ok(out);
+        }
+
+        for _ in 0 .. self.roll_count(luck, rng) {
+            let entry = *list.select_random_value(rng)?;
+
+            if *entry != MinecraftEntry::Empty {
+                out.push(entry);
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+impl MinecraftNumber
+{
+    /// Get a random number from the provider.
+    pub fn sample<RNG>(&self, rng: &mut RNG) -> f64
+        where RNG: Rng + ?Sized
+    {
+        match *self {
+            Self::Constant(value) => value,
+            Self::Uniform { min, max } if min < max => rng.random_range(min .. max),
+            Self::Uniform { min, .. } => min,
+            Self::Binomial { n, p } => (0..n).filter(|_| rng.random_bool(p.clamp(0.0, 1.0))).count() as f64,
+        }
+    }
+
+    /// Get a random integer from the provider, where uniform numbers are chosen between their rounded-down bounds.
+    pub fn sample_int<RNG>(&self, rng: &mut RNG) -> i64
+        where RNG: Rng + ?Sized
+    {
+        match *self {
+            Self::Uniform { min, max } => {
+                let (min, max) = (min.floor() as i64, max.floor() as i64);
+                if min < max { rng.random_range(min ..= max) } else { min }
+            },
+            _ => self.sample(rng).floor() as i64,
+        }
+    }
+}
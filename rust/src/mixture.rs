@@ -0,0 +1,170 @@
+use std::error::{ Error };
+
+use rand::prelude::*;
+
+use crate::*;
+use crate::errors::*;
+
+
+/// A weighted mixture of [`WeightedList`]s, which selects a list in proportion to its weight, then selects an item from that list.
+///
+/// Unlike [`WeightedList::flatten()`], the lists are never combined into a single list, so constructing and updating a mixture is cheap and preserves the exact weights of each list.
+///
+/// # Usage
+///
+/// ```
+/// # use weighted_list::*;
+/// let mut mixture = Mixture::new();
+///
+/// mixture
+///     .push_component(3, wlist![(1, "wolf"), (2, "spider")])
+///     .push_component(1, wlist![(5, "husk")]);
+///
+/// let mob = mixture.select_random_value(&mut rand::rng())?;
+/// // could give:
+/// //   - "wolf"   with 25% probability
+/// //   - "spider" with 50% probability
+/// //   - "husk"   with 25% probability
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Mixture<V, W: Weight>
+{
+    components: WeightedList<WeightedList<V,W>, W>,
+}
+
+// == CONSTRUCTORS == //
+impl<V, W: Weight> Mixture<V,W>
+{
+    /// Construct an empty [`Mixture`] with no components.
+    pub fn new() -> Self
+    {
+        Self { components: WeightedList::new() }
+    }
+
+    /// Add a component `list`, selected with a relative probability of `weight`.
+    pub fn push_component(&mut self, weight: W, list: WeightedList<V,W>) -> &mut Self
+    {
+        self.components.push_new_item(weight, list);
+        self
+    }
+}
+
+impl<V, W: Weight> Default for Mixture<V,W>
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl<V, W: Weight> From<WeightedList<WeightedList<V,W>, W>> for Mixture<V,W>
+{
+    fn from(components: WeightedList<WeightedList<V,W>, W>) -> Self
+    {
+        Self { components }
+    }
+}
+
+impl<V, W: Weight> FromIterator<(W, WeightedList<V,W>)> for Mixture<V,W>
+{
+    fn from_iter<I>(iter: I) -> Self
+        where I: IntoIterator<Item = (W, WeightedList<V,W>)>
+    {
+        Self { components: iter.into_iter().collect() }
+    }
+}
+
+// == ACCESSORS == //
+impl<V, W: Weight> Mixture<V,W>
+{
+    /// Get the component lists of the mixture, weighted by their relative probabilities.
+    pub fn components(&self) -> &WeightedList<WeightedList<V,W>, W>
+    {
+        &self.components
+    }
+
+    /// Consume the mixture and return its component lists.
+    pub fn into_components(self) -> WeightedList<WeightedList<V,W>, W>
+    {
+        self.components
+    }
+
+    /// Flatten the mixture into a single [`WeightedList`].
+    ///
+    /// See [`WeightedList::flatten()`] for details.
+    pub fn flatten(&self) -> Result<WeightedList<V, f64>, NumCastFailure>
+        where V: Clone
+    {
+        self.components.flatten()
+    }
+}
+
+// == RANDOMISATION == //
+impl<V, W: Weight> Mixture<V,W>
+{
+    /// Select a random component list in proportion to its weight, then select a random item from it, using weighted randomisation.
+    ///
+    /// Components with a weight of zero, or a total weight of zero, are never selected.
+    pub fn select_random_item<RNG>(&self, rng: &mut RNG) -> Result<&WeightedItem<V,W>, Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        let selectable = || self.components.iter()
+            .filter(|component| component.weight > W::zero() && !component.value.is_zero());
+
+        let total = selectable().map(|component| component.weight).sum::<W>();
+
+        if total <= W::zero() {
+            Err(Box::new(EmptyWeightedList { reason: "Cannot select a random item from a `Mixture` with no selectable components" }))?
+        }
+
+        let mut idx = self.components._get_random_weighted_index_up_to_(rng, total)?;
+
+        // fall back to the last component in case of float rounding
+        let component = selectable()
+            .find(|component| {
+                if idx < component.weight { return true }
+                idx -= component.weight;
+                false
+            })
+            .or_else(|| selectable().last())
+            .expect("total weight is positive, so some component is selectable");
+
+        component.value.select_random_item(rng)
+    }
+
+    /// Select a random component list in proportion to its weight, then select a random item from it and return its value.
+    ///
+    /// See [`.select_random_item()`](Self::select_random_item) for details.
+    pub fn select_random_value<RNG>(&self, rng: &mut RNG) -> Result<&V, Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        self.select_random_item(rng).map(|item| &item.value)
+    }
+}
+
+// == SERDE == //
+#[cfg(feature = "serde")]
+impl<V, W> serde::Serialize for Mixture<V,W>
+    where
+        V: serde::Serialize,
+        W: Weight + serde::Serialize,
+{
+    /// Serialises as the component lists, weighted by their relative probabilities.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        self.components.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, V, W> serde::Deserialize<'de> for Mixture<V,W>
+    where
+        V: serde::Deserialize<'de>,
+        W: Weight + serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        WeightedList::deserialize(deserializer).map(Self::from)
+    }
+}
@@ -0,0 +1,531 @@
+//! Serde representations for [`WeightedList`](crate::WeightedList) and related types, enabled by the `serde` feature.
+//!
+//! By default, lists serialise as a sequence of `[weight, value]` pairs. The submodules here provide alternative representations, which can be selected with `#[serde(with = "...")]`:
+//!
+//! | module | representation |
+//! | :----- | :------------- |
+//! | [`pairs`] | `[[2, "sup"], [3, "nova"]]` (the default) |
+//! | [`map`] | `{"sup": 2, "nova": 3}` |
+//! | [`items`] | `[{"weight": 2, "value": "sup"}, {"weight": 3, "value": "nova"}]` |
+//!
+//! Each works with any [`WeightedCollection`].
+//!
+//! With the `schemars` feature, each module also has a `json_schema()` function describing its representation, which can be selected with `#[schemars(schema_with = "...")]`. Weights in the schema must be non-negative.
+//!
+//! # Usage
+//!
+//! ```
+//! # use weighted_list::*;
+//! use serde::{ Serialize, Deserialize };
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct DropTable
+//! {
+//!     #[serde(with = "weighted_list::repr::map")]
+//!     drops: WeightedList<String, u32>,
+//! }
+//! ```
+//!
+//! ```
+//! # #[cfg(feature = "schemars")] {
+//! # use weighted_list::*;
+//! use serde::{ Serialize, Deserialize };
+//! use schemars::{ JsonSchema };
+//!
+//! #[derive(Serialize, Deserialize, JsonSchema)]
+//! struct DropTable
+//! {
+//!     #[serde(with = "weighted_list::repr::map")]
+//!     #[schemars(schema_with = "weighted_list::repr::map::json_schema::<String, u32>")]
+//!     drops: WeightedList<String, u32>,
+//! }
+//! # }
+//! ```
+
+use std::fmt;
+use std::hash::{ Hash };
+use std::marker::{ PhantomData };
+
+use serde::{ Serialize, Serializer, Deserialize, Deserializer };
+use serde::de::{ self, MapAccess, Visitor };
+
+#[cfg(feature = "schemars")]
+use schemars::{ JsonSchema, Schema, SchemaGenerator };
+#[cfg(feature = "schemars")]
+use std::borrow::{ Cow };
+
+use crate::*;
+
+
+/// A collection of weighted items that can be serialised with any of the representations in [`repr`](self).
+///
+/// This trait is sealed, and implemented for [`WeightedList`], [`WeightedMap`] and `FrozenWeightedList`.
+pub trait WeightedCollection<V, W: Weight>: Sized + sealed::Sealed
+{
+    #[doc(hidden)]
+    fn _serde_raw_<'c>(&'c self) -> impl Iterator<Item = (W, &'c V)>
+        where V: 'c;
+
+    #[doc(hidden)]
+    fn _serde_from_raw_<E: de::Error>(items: Vec<(W, V)>) -> Result<Self, E>;
+}
+
+mod sealed
+{
+    pub trait Sealed {}
+}
+
+
+impl<V, W: Weight> sealed::Sealed for WeightedList<V,W> {}
+
+impl<V, W: Weight> WeightedCollection<V,W> for WeightedList<V,W>
+{
+    fn _serde_raw_<'c>(&'c self) -> impl Iterator<Item = (W, &'c V)>
+        where V: 'c
+    {
+        self.raw()
+    }
+
+    fn _serde_from_raw_<E: de::Error>(items: Vec<(W, V)>) -> Result<Self, E>
+    {
+        Ok(items.into_iter().collect())
+    }
+}
+
+impl<V, W: Weight> sealed::Sealed for WeightedMap<V,W> {}
+
+impl<V, W: Weight> WeightedCollection<V,W> for WeightedMap<V,W>
+    where V: Clone + Eq + Hash
+{
+    fn _serde_raw_<'c>(&'c self) -> impl Iterator<Item = (W, &'c V)>
+        where V: 'c
+    {
+        self.raw()
+    }
+
+    /// Items with duplicate values are merged.
+    fn _serde_from_raw_<E: de::Error>(items: Vec<(W, V)>) -> Result<Self, E>
+    {
+        Ok(items.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "frozen")]
+impl<V, W: Weight> sealed::Sealed for FrozenWeightedList<V,W> {}
+
+#[cfg(feature = "frozen")]
+impl<V, W: Weight> WeightedCollection<V,W> for FrozenWeightedList<V,W>
+{
+    fn _serde_raw_<'c>(&'c self) -> impl Iterator<Item = (W, &'c V)>
+        where V: 'c
+    {
+        self.raw()
+    }
+
+    /// Cumulative weights are never read from the input, but recomputed from the weights, which must be non-negative and sum without overflowing.
+    fn _serde_from_raw_<E: de::Error>(items: Vec<(W, V)>) -> Result<Self, E>
+    {
+        let mut total = W::zero();
+
+        for (i, (weight, _)) in items.iter().enumerate() {
+            if weight.partial_cmp(&W::zero()).is_none_or(|order| order.is_lt()) {
+                return Err(E::custom(format!("item {i} has invalid weight {weight:?}, but weights must be non-negative")));
+            }
+
+            total = util::checked_add(total, *weight).map_err(E::custom)?;
+        }
+
+        Ok(FrozenWeightedList::init(items))
+    }
+}
+
+
+/// The default representation, as a sequence of `[weight, value]` pairs.
+///
+/// ```json
+/// [[2, "sup"], [3, "nova"]]
+/// ```
+pub mod pairs
+{
+    use super::*;
+
+    pub fn serialize<C, V, W, S>(collection: &C, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            C: WeightedCollection<V,W>,
+            V: Serialize,
+            W: Weight + Serialize,
+            S: Serializer,
+    {
+        serializer.collect_seq(collection._serde_raw_())
+    }
+
+    pub fn deserialize<'de, C, V, W, D>(deserializer: D) -> Result<C, D::Error>
+        where
+            C: WeightedCollection<V,W>,
+            V: Deserialize<'de>,
+            W: Weight + Deserialize<'de>,
+            D: Deserializer<'de>,
+    {
+        let items = Vec::<(W, V)>::deserialize(deserializer)?;
+        C::_serde_from_raw_(items)
+    }
+
+    /// The JSON Schema of this representation, an array of `[weight, value]` arrays.
+    #[cfg(feature = "schemars")]
+    pub fn json_schema<V, W>(generator: &mut SchemaGenerator) -> Schema
+        where
+            V: JsonSchema,
+            W: Weight + JsonSchema,
+    {
+        Vec::<(NonNegative<W>, V)>::json_schema(generator)
+    }
+}
+
+/// A representation as a map from values to weights, preserving order.
+///
+/// ```json
+/// {"sup": 2, "nova": 3}
+/// ```
+///
+/// Values must be usable as keys by the data format. Items with duplicate values are written as duplicate keys, and read back as separate items.
+pub mod map
+{
+    use super::*;
+
+    pub fn serialize<C, V, W, S>(collection: &C, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            C: WeightedCollection<V,W>,
+            V: Serialize,
+            W: Weight + Serialize,
+            S: Serializer,
+    {
+        serializer.collect_map(collection._serde_raw_().map(|(weight, value)| (value, weight)))
+    }
+
+    pub fn deserialize<'de, C, V, W, D>(deserializer: D) -> Result<C, D::Error>
+        where
+            C: WeightedCollection<V,W>,
+            V: Deserialize<'de>,
+            W: Weight + Deserialize<'de>,
+            D: Deserializer<'de>,
+    {
+        let items = deserializer.deserialize_map(MapVisitor(PhantomData))?;
+        C::_serde_from_raw_(items)
+    }
+
+    /// The JSON Schema of this representation, an object with weights as its values.
+    ///
+    /// Keys are always strings in JSON, so the schema does not depend on `V`.
+    #[cfg(feature = "schemars")]
+    pub fn json_schema<V, W>(generator: &mut SchemaGenerator) -> Schema
+        where W: Weight + JsonSchema
+    {
+        std::collections::BTreeMap::<String, NonNegative<W>>::json_schema(generator)
+    }
+
+    struct MapVisitor<V, W>(PhantomData<(V, W)>);
+
+    impl<'de, V, W> Visitor<'de> for MapVisitor<V,W>
+        where
+            V: Deserialize<'de>,
+            W: Deserialize<'de>,
+    {
+        type Value = Vec<(W, V)>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            write!(f, "a map of values to weights")
+        }
+
+        fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+            where A: MapAccess<'de>
+        {
+            let mut out = Vec::with_capacity(access.size_hint().unwrap_or(0));
+
+            while let Some((value, weight)) = access.next_entry()? {
+                out.push((weight, value));
+            }
+
+            Ok(out)
+        }
+    }
+}
+
+/// A representation as a sequence of structs with `weight` and `value` fields.
+///
+/// ```json
+/// [{"weight": 2, "value": "sup"}, {"weight": 3, "value": "nova"}]
+/// ```
+pub mod items
+{
+    use super::*;
+
+    #[derive(Serialize)]
+    struct ItemRef<'v, V, W>
+    {
+        weight: W,
+        value: &'v V,
+    }
+
+    #[derive(Deserialize)]
+    struct Item<V, W>
+    {
+        weight: W,
+        value: V,
+    }
+
+    pub fn serialize<C, V, W, S>(collection: &C, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            C: WeightedCollection<V,W>,
+            V: Serialize,
+            W: Weight + Serialize,
+            S: Serializer,
+    {
+        serializer.collect_seq(
+            collection._serde_raw_().map(|(weight, value)| ItemRef { weight, value })
+        )
+    }
+
+    pub fn deserialize<'de, C, V, W, D>(deserializer: D) -> Result<C, D::Error>
+        where
+            C: WeightedCollection<V,W>,
+            V: Deserialize<'de>,
+            W: Weight + Deserialize<'de>,
+            D: Deserializer<'de>,
+    {
+        let items = Vec::<Item<V,W>>::deserialize(deserializer)?;
+        C::_serde_from_raw_(items.into_iter().map(|item| (item.weight, item.value)).collect())
+    }
+
+    /// The JSON Schema of this representation, an array of objects with `weight` and `value` fields.
+    #[cfg(feature = "schemars")]
+    pub fn json_schema<V, W>(generator: &mut SchemaGenerator) -> Schema
+        where
+            V: JsonSchema,
+            W: Weight + JsonSchema,
+    {
+        schemars::json_schema!({
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "weight": generator.subschema_for::<NonNegative<W>>(),
+                    "value": generator.subschema_for::<V>(),
+                },
+                "required": ["weight", "value"],
+            },
+        })
+    }
+}
+
+
+// == DEFAULT IMPLEMENTATIONS == //
+impl<V, W> Serialize for WeightedItem<V,W>
+    where
+        V: Serialize,
+        W: Weight + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        (self.weight, &self.value).serialize(serializer)
+    }
+}
+
+impl<'de, V, W> Deserialize<'de> for WeightedItem<V,W>
+    where
+        V: Deserialize<'de>,
+        W: Weight + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        let (weight, value) = <(W, V)>::deserialize(deserializer)?;
+        Ok(WeightedItem::new(weight, value))
+    }
+}
+
+impl<V, W> Serialize for WeightedList<V,W>
+    where
+        V: Serialize,
+        W: Weight + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        pairs::serialize(self, serializer)
+    }
+}
+
+impl<'de, V, W> Deserialize<'de> for WeightedList<V,W>
+    where
+        V: Deserialize<'de>,
+        W: Weight + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        pairs::deserialize(deserializer)
+    }
+}
+
+impl<V, W> Serialize for WeightedMap<V,W>
+    where
+        V: Serialize,
+        W: Weight + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.collect_seq(self.raw())
+    }
+}
+
+impl<'de, V, W> Deserialize<'de> for WeightedMap<V,W>
+    where
+        V: Deserialize<'de> + Clone + Eq + Hash,
+        W: Weight + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        pairs::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "frozen")]
+impl<V, W> Serialize for FrozenWeightedItem<V,W>
+    where
+        V: Serialize,
+        W: Weight + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        (self.weight(), self.value()).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "frozen")]
+impl<'de, V, W> Deserialize<'de> for FrozenWeightedItem<V,W>
+    where
+        V: Deserialize<'de>,
+        W: Weight + Deserialize<'de>,
+{
+    /// As a standalone item, the cumulative weight is the weight itself.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        let (weight, value) = <(W, V)>::deserialize(deserializer)?;
+        Ok(FrozenWeightedItem::new(weight, weight, value))
+    }
+}
+
+#[cfg(feature = "frozen")]
+impl<V, W> Serialize for FrozenWeightedList<V,W>
+    where
+        V: Serialize,
+        W: Weight + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        pairs::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "frozen")]
+impl<'de, V, W> Deserialize<'de> for FrozenWeightedList<V,W>
+    where
+        V: Deserialize<'de>,
+        W: Weight + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        pairs::deserialize(deserializer)
+    }
+}
+
+
+// == JSON SCHEMA == //
+/// The schema of `W`, restricted to non-negative values.
+#[cfg(feature = "schemars")]
+struct NonNegative<W>(PhantomData<W>);
+
+#[cfg(feature = "schemars")]
+impl<W: JsonSchema> JsonSchema for NonNegative<W>
+{
+    fn inline_schema() -> bool
+    {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str>
+    {
+        format!("NonNegative_{}", W::schema_name()).into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema
+    {
+        let mut schema = W::json_schema(generator);
+        schema.insert("minimum".to_string(), 0.into());
+        schema
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<V, W> JsonSchema for WeightedItem<V,W>
+    where
+        V: JsonSchema,
+        W: Weight + JsonSchema,
+{
+    fn schema_name() -> Cow<'static, str>
+    {
+        format!("WeightedItem_for_{}_and_{}", V::schema_name(), W::schema_name()).into()
+    }
+
+    fn schema_id() -> Cow<'static, str>
+    {
+        format!("weighted_list::WeightedItem<{}, {}>", V::schema_id(), W::schema_id()).into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema
+    {
+        <(NonNegative<W>, V)>::json_schema(generator)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<V, W> JsonSchema for WeightedList<V,W>
+    where
+        V: JsonSchema,
+        W: Weight + JsonSchema,
+{
+    fn schema_name() -> Cow<'static, str>
+    {
+        format!("WeightedList_for_{}_and_{}", V::schema_name(), W::schema_name()).into()
+    }
+
+    fn schema_id() -> Cow<'static, str>
+    {
+        format!("weighted_list::WeightedList<{}, {}>", V::schema_id(), W::schema_id()).into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema
+    {
+        pairs::json_schema::<V,W>(generator)
+    }
+}
+
+#[cfg(all(feature = "schemars", feature = "frozen"))]
+impl<V, W> JsonSchema for FrozenWeightedList<V,W>
+    where
+        V: JsonSchema,
+        W: Weight + JsonSchema,
+{
+    fn schema_name() -> Cow<'static, str>
+    {
+        format!("FrozenWeightedList_for_{}_and_{}", V::schema_name(), W::schema_name()).into()
+    }
+
+    fn schema_id() -> Cow<'static, str>
+    {
+        format!("weighted_list::FrozenWeightedList<{}, {}>", V::schema_id(), W::schema_id()).into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema
+    {
+        pairs::json_schema::<V,W>(generator)
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::{ HashMap, HashSet };
+use std::fs;
+use std::hash::{ DefaultHasher, Hash, Hasher };
+use std::path::{ Path, PathBuf };
+use std::str::{ FromStr };
+use std::sync::{ Arc, Mutex, RwLock, Weak };
+use std::thread;
+use std::time::{ Duration, SystemTime };
+
+use crate::*;
+use crate::errors::*;
+
+
+/// How long after a file is modified a same-sized rewrite may still share its modification time, since some filesystems only record modification times to the nearest 2 seconds.
+const MTIME_GRANULARITY: Duration = Duration::from_secs(2);
+
+
+/// A registry of named tables loaded from a directory, which can be reloaded while in use.
+///
+/// Each `.txt` file in the directory is parsed as a [`FrozenWeightedList`] in the text format (see [`WeightedList`]'s `FromStr` implementation), and named by its file name without the extension. Other files and subdirectories are ignored.
+///
+/// Tables are handed out as [`Arc`] snapshots. Reloading a table swaps in a new snapshot, so readers still holding the old one are unaffected.
+///
+/// # Usage
+///
+/// ```
+/// # use weighted_list::*;
+/// # let dir = std::env::temp_dir().join(format!("weighted-list-doctest-registry-{}", std::process::id()));
+/// # std::fs::create_dir_all(&dir)?;
+/// std::fs::write(dir.join("chest.txt"), "5  stick\n1  diamond\n")?;
+///
+/// let registry = TableRegistry::<String, u32>::new(&dir);
+/// assert!( registry.poll().is_empty() );
+///
+/// let chest = registry.get("chest").unwrap();
+/// assert_eq!( chest.len(), 6 );
+///
+/// std::fs::write(dir.join("chest.txt"), "5  stick\n10  diamond\n")?;
+/// registry.poll();
+///
+/// assert_eq!( registry.get("chest").unwrap().len(), 15 );
+/// assert_eq!( chest.len(), 6 );
+/// # std::fs::remove_dir_all(&dir)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct TableRegistry<V, W: Weight>
+{
+    dir: PathBuf,
+    tables: RwLock<HashMap<String, Arc<FrozenWeightedList<V,W>>>>,
+    /// The state of each file when it was last read.
+    files: Mutex<HashMap<String, FileState>>,
+}
+
+/// The modification time and size of a file.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Stamp
+{
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+/// The state of a file when it was last read.
+#[derive(Debug)]
+struct FileState
+{
+    stamp: Stamp,
+    /// Hash of the contents of the file.
+    hash: u64,
+    /// Whether the file was read long enough after it was modified that any later change will also change its [`Stamp`].
+    settled: bool,
+}
+
+
+// == CONSTRUCTORS == //
+impl<V, W: Weight> TableRegistry<V,W>
+{
+    /// Construct an empty registry for tables in `dir`.
+    ///
+    /// No tables are loaded until [`::poll()`](Self::poll) is called.
+    pub fn new(dir: impl Into<PathBuf>) -> Self
+    {
+        Self {
+            dir: dir.into(),
+            tables: RwLock::new(HashMap::new()),
+            files: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+// == ACCESSORS == //
+impl<V, W: Weight> TableRegistry<V,W>
+{
+    /// Get the directory tables are loaded from.
+    pub fn dir(&self) -> &Path
+    {
+        &self.dir
+    }
+
+    /// Get a snapshot of the table named `name`, or `None` if there is no such table.
+    ///
+    /// The snapshot is not affected by later reloads.
+    pub fn get(&self, name: &str) -> Option<Arc<FrozenWeightedList<V,W>>>
+    {
+        self.tables.read().expect("registry lock should not be poisoned").get(name).cloned()
+    }
+
+    /// Get the names of the loaded tables, in alphabetical order.
+    pub fn names(&self) -> Vec<String>
+    {
+        let mut out = self.tables.read().expect("registry lock should not be poisoned")
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        out.sort();
+        out
+    }
+}
+
+// == RELOADING == //
+impl<V, W: Weight> TableRegistry<V,W>
+    where
+        V: FromStr,
+        W: FromStr,
+{
+    /// Check the directory for changes, loading new tables, reloading changed tables and removing tables whose files were deleted.
+    ///
+    /// Files are only read when their modification time or size has changed since they were last read. Since modification times may be too coarse to notice quick rewrites, files modified within 2 seconds of when they were last read are read again too. Files that are read are reloaded only if their contents have changed, which is detected by hashing them. Each table is swapped in atomically, so readers see either the old or the new snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TableLoadError`] for each file that could not be read or parsed, in which case the last good version of its table is kept. Parse errors are reported once, until the file changes again.
+    pub fn poll(&self) -> Vec<TableLoadError>
+    {
+        let mut files = self.files.lock().expect("registry lock should not be poisoned");
+        let mut errors = Vec::new();
+
+        let error = |path: &Path, reason: String| TableLoadError { path: path.to_path_buf(), reason };
+
+        let mut paths = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| match entry {
+                    Ok(entry) => Some(entry.path()),
+                    Err(err) => { errors.push(error(&self.dir, err.to_string())); None },
+                })
+                .collect::<Vec<_>>(),
+            Err(err) => return vec![error(&self.dir, err.to_string())],
+        };
+
+        paths.sort();
+
+        let mut seen = HashSet::new();
+
+        for path in paths {
+            if path.extension().is_none_or(|ext| ext != "txt") { continue }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else { continue };
+
+            // a file that cannot be read is kept, the same as one that cannot be parsed
+            let stamp = match fs::metadata(&path) {
+                Ok(metadata) if !metadata.is_file() => continue,
+                Ok(metadata) => Stamp { modified: metadata.modified().ok(), len: metadata.len() },
+                Err(err) => {
+                    seen.insert(name.to_string());
+                    errors.push(error(&path, err.to_string()));
+                    continue
+                },
+            };
+
+            seen.insert(name.to_string());
+
+            if files.get(name).is_some_and(|file| file.settled && file.stamp == stamp) { continue }
+
+            let read_at = SystemTime::now();
+
+            let source = match fs::read(&path) {
+                Ok(source) => source,
+                Err(err) => { errors.push(error(&path, err.to_string())); continue },
+            };
+
+            let mut hasher = DefaultHasher::new();
+            source.hash(&mut hasher);
+            let hash = hasher.finish();
+
+            let settled = stamp.modified.is_some_and(|modified| {
+                read_at.duration_since(modified).is_ok_and(|age| age >= MTIME_GRANULARITY)
+            });
+
+            let unchanged = files.get(name).is_some_and(|file| file.hash == hash);
+            files.insert(name.to_string(), FileState { stamp, hash, settled });
+
+            if unchanged { continue }
+
+            let table = String::from_utf8(source)
+                .map_err(|err| err.to_string())
+                .and_then(|source| source.parse::<FrozenWeightedList<V,W>>().map_err(|err| err.to_string()));
+
+            match table {
+                Ok(table) => {
+                    self.tables.write().expect("registry lock should not be poisoned")
+                        .insert(name.to_string(), Arc::new(table));
+                },
+                Err(reason) => errors.push(error(&path, reason)),
+            }
+        }
+
+        files.retain(|name, _| seen.contains(name));
+        self.tables.write().expect("registry lock should not be poisoned")
+            .retain(|name, _| seen.contains(name));
+
+        errors
+    }
+
+    /// Spawn a thread which calls [`::poll()`](Self::poll) every `interval`, passing each error to `on_error`.
+    ///
+    /// The thread stops once every other [`Arc`] of the registry has been dropped.
+    ///
+    /// # Usage
+    ///
+    /// ```no_run
+    /// # use weighted_list::*;
+    /// # use std::sync::Arc;
+    /// # use std::time::Duration;
+    /// let registry = Arc::new(TableRegistry::<String, u32>::new("tables"));
+    ///
+    /// registry.watch(Duration::from_secs(1), |err| eprintln!("{err}"));
+    /// ```
+    pub fn watch<F>(self: &Arc<Self>, interval: Duration, mut on_error: F) -> thread::JoinHandle<()>
+        where
+            V: Send + Sync + 'static,
+            W: Send + Sync + 'static,
+            F: FnMut(TableLoadError) + Send + 'static,
+    {
+        let registry: Weak<Self> = Arc::downgrade(self);
+
+        thread::spawn(move || {
+            while let Some(registry) = registry.upgrade() {
+                registry.poll().into_iter().for_each(&mut on_error);
+                drop(registry);
+
+                thread::sleep(interval);
+            }
+        })
+    }
+}
@@ -0,0 +1,193 @@
+use std::fmt::{ self, Display };
+use std::str::{ FromStr };
+
+use crate::*;
+use crate::errors::*;
+
+
+/// Parse the text format of [`WeightedList`] into `(weight, value)` pairs.
+///
+/// If `validate` is set, weights must also be non-negative and sum without overflowing.
+pub(crate) fn parse_items<V, W>(source: &str, validate: bool) -> Result<Vec<(W, V)>, ListParseError>
+    where
+        V: FromStr,
+        W: Weight + FromStr,
+{
+    let mut out = Vec::new();
+    let mut total = W::zero();
+    let mut percentages = None::<bool>;
+
+    for (i, line) in source.lines().enumerate() {
+        let error = |column: usize, reason| ListParseError { line: i + 1, column: column + 1, reason };
+
+        let chars = line.chars().collect::<Vec<char>>();
+        let mut pos = skip_whitespace(&chars, 0);
+
+        if pos == chars.len() || is_comment(&chars, pos) { continue }
+
+        let weight_column = pos;
+        while pos < chars.len() && !chars[pos].is_whitespace() && !"|%".contains(chars[pos]) {
+            pos += 1;
+        }
+
+        if pos == weight_column {
+            return Err(error(pos, "Expected a weight before the value"));
+        }
+
+        let weight = chars[weight_column..pos].iter().collect::<String>()
+            .parse::<W>()
+            .map_err(|_| error(weight_column, "Invalid weight"))?;
+
+        let is_percentage = chars.get(pos) == Some(&'%');
+        if is_percentage {
+            pos += 1;
+        }
+
+        if *percentages.get_or_insert(is_percentage) != is_percentage {
+            return Err(error(weight_column, "Cannot mix percentages with plain weights"));
+        }
+
+        if validate {
+            if weight.partial_cmp(&W::zero()).is_none_or(|order| order.is_lt()) {
+                return Err(error(weight_column, "Weights must be non-negative"));
+            }
+
+            total = util::checked_add(total, weight)
+                .map_err(|_| error(weight_column, "Total weight overflows the weight type"))?;
+        }
+
+        if pos < chars.len() && !chars[pos].is_whitespace() && chars[pos] != '|' {
+            return Err(error(pos, "Expected whitespace or `|` after the weight"));
+        }
+
+        pos = skip_whitespace(&chars, pos);
+        if chars.get(pos) == Some(&'|') {
+            pos = skip_whitespace(&chars, pos + 1);
+        }
+
+        if pos == chars.len() || is_comment(&chars, pos) {
+            return Err(error(pos, "Expected a value after the weight"));
+        }
+
+        let value_column = pos;
+        let value =
+            if chars[pos] == '"' {
+                let (value, end) = parse_quoted(&chars, pos).map_err(|(column, reason)| error(column, reason))?;
+
+                let rest = skip_whitespace(&chars, end);
+                if rest < chars.len() && !is_comment(&chars, rest) {
+                    return Err(error(rest, "Unexpected text after quoted value"));
+                }
+
+                value
+            }
+            else {
+                let end = (pos .. chars.len())
+                    .find(|&idx| chars[idx - 1].is_whitespace() && is_comment(&chars, idx))
+                    .unwrap_or(chars.len());
+
+                chars[pos..end].iter().collect::<String>().trim_end().to_string()
+            };
+
+        let value = value.parse::<V>().map_err(|_| error(value_column, "Invalid value"))?;
+
+        out.push((weight, value));
+    }
+
+    Ok(out)
+}
+
+fn skip_whitespace(chars: &[char], mut pos: usize) -> usize
+{
+    while pos < chars.len() && chars[pos].is_whitespace() {
+        pos += 1;
+    }
+    pos
+}
+
+fn is_comment(chars: &[char], pos: usize) -> bool
+{
+    chars[pos..].starts_with(&['/', '/'])
+}
+
+/// Parse a quoted value starting at `start`, returning it and the position after the closing quote.
+fn parse_quoted(chars: &[char], start: usize) -> Result<(String, usize), (usize, &'static str)>
+{
+    let mut out = String::new();
+    let mut pos = start + 1;
+
+    while pos < chars.len() {
+        match chars[pos] {
+            '"' => return Ok((out, pos + 1)),
+            '\\' => {
+                out.push(
+                    match chars.get(pos + 1) {
+                        Some('"')  => '"',
+                        Some('\\') => '\\',
+                        Some('n')  => '\n',
+                        Some('r')  => '\r',
+                        Some('t')  => '\t',
+                        _ => return Err((pos, "Invalid escape sequence")),
+                    }
+                );
+                pos += 2;
+            },
+            char => {
+                out.push(char);
+                pos += 1;
+            },
+        }
+    }
+
+    Err((start, "Unterminated quoted value"))
+}
+
+
+/// Write `(weight, value)` pairs in the text format of [`WeightedList`], one per line with weights aligned.
+pub(crate) fn write_items<'v, V, W>(f: &mut fmt::Formatter<'_>, items: impl Iterator<Item = (W, &'v V)>) -> fmt::Result
+    where
+        V: Display + 'v,
+        W: Display,
+{
+    let lines = items
+        .map(|(weight, value)| (weight.to_string(), quote_if_needed(value.to_string())))
+        .collect::<Vec<(String, String)>>();
+
+    let width = lines.iter().map(|(weight, _)| weight.chars().count()).max().unwrap_or(0);
+
+    for (weight, value) in lines {
+        writeln!(f, "{weight:<width$}  {value}")?;
+    }
+
+    Ok(())
+}
+
+/// Quote a value if it would otherwise be parsed differently, such as if it has surrounding whitespace or contains a comment.
+fn quote_if_needed(value: String) -> String
+{
+    let needs_quotes =
+        value.is_empty()
+        || value.starts_with(['"', '|'])
+        || value.starts_with(char::is_whitespace)
+        || value.ends_with(char::is_whitespace)
+        || value.contains("//")
+        || value.contains(['\n', '\r']);
+
+    if !needs_quotes { return value }
+
+    let mut out = String::from('"');
+
+    for char in value.chars() {
+        match char {
+            '"'  => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            char => out.push(char),
+        }
+    }
+
+    out.push('"');
+    out
+}
@@ -1,9 +1,9 @@
 use std::fmt::Debug;
 
-use num_traits;
 use num_traits::NumCast;
 
 use crate::errors::*;
+use crate::Weight;
 
 
 pub fn try_cast<T, R>(n: T) -> Result<R, NumCastFailure>
@@ -19,3 +19,90 @@ pub fn try_cast<T, R>(n: T) -> Result<R, NumCastFailure>
             }
         )
 }
+
+
+/// Whether `W` is an integer type, in which case division truncates.
+pub fn is_integral<W: Weight>() -> bool
+{
+    (W::one() / (W::one() + W::one())).is_zero()
+}
+
+/// Apply a weight operation exactly, reporting an error rather than panicking or wrapping on overflow.
+/// 
+/// Integer weights are checked via `i128`, falling back to `u128` for unsigned values beyond its range, while float weights overflow if they become non-finite.
+fn checked_op<W: Weight>(
+    lhs: W,
+    rhs: W,
+    operation: &'static str,
+    int_op: fn(i128, i128) -> Option<i128>,
+    uint_op: fn(u128, u128) -> Option<u128>,
+    float_op: fn(W, W) -> W,
+) -> Result<W, WeightOverflow>
+{
+    let overflow = || WeightOverflow {
+        operation,
+        lhs: format!("{lhs:?}"),
+        rhs: format!("{rhs:?}"),
+    };
+
+    if is_integral::<W>() {
+        lhs.to_i128()
+            .zip(rhs.to_i128())
+            .and_then(|(l, r)| int_op(l, r))
+            .and_then(W::from)
+            .or_else(||
+                lhs.to_u128()
+                    .zip(rhs.to_u128())
+                    .and_then(|(l, r)| uint_op(l, r))
+                    .and_then(W::from)
+            )
+            .ok_or_else(overflow)
+    }
+    else {
+        let out = float_op(lhs, rhs);
+
+        if out.to_f64().is_none_or(f64::is_finite)
+            || lhs.to_f64().is_some_and(|l| !l.is_finite())
+            || rhs.to_f64().is_some_and(|r| !r.is_finite())
+        {
+            Ok(out)
+        } else {
+            Err(overflow())
+        }
+    }
+}
+
+pub fn checked_add<W: Weight>(lhs: W, rhs: W) -> Result<W, WeightOverflow>
+{
+    checked_op(lhs, rhs, "adding", i128::checked_add, u128::checked_add, |l, r| l + r)
+}
+
+pub fn checked_mul<W: Weight>(lhs: W, rhs: W) -> Result<W, WeightOverflow>
+{
+    checked_op(lhs, rhs, "multiplying", i128::checked_mul, u128::checked_mul, |l, r| l * r)
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test] fn checked_ops()
+    {
+        assert_eq!( checked_mul(16u8, 15u8).ok(), Some(240) );
+        assert!( checked_mul(16u8, 16u8).is_err() );
+        assert!( checked_add(255u8, 1u8).is_err() );
+        assert_eq!( checked_add(-3i32, 2i32).ok(), Some(-1) );
+        assert!( checked_mul(u64::MAX, 2u64).is_err() );
+
+        assert_eq!( checked_mul(u128::MAX / 2, 2u128).ok(), Some(u128::MAX - 1) );
+        assert_eq!( checked_add(u128::MAX - 1, 1u128).ok(), Some(u128::MAX) );
+        assert!( checked_mul(u128::MAX / 2, 3u128).is_err() );
+        assert!( checked_mul(i128::MAX, 2i128).is_err() );
+
+        assert_eq!( checked_mul(0.5f64, 3.0).ok(), Some(1.5) );
+        assert!( checked_mul(f64::MAX, 2.0).is_err() );
+        assert!( checked_add(f64::INFINITY, 1.0).is_ok() );
+    }
+}
@@ -1,7 +1,10 @@
-use std::collections::{ HashSet };
+use std::cmp::{ Ordering, Reverse };
+use std::collections::{ BTreeMap, BinaryHeap, HashMap, HashSet };
 use std::error::{ Error };
 use std::fmt::{ Debug, Display };
 use std::hash::{ Hash };
+use std::ops::{ self, Bound, Range, RangeBounds };
+use std::str::{ FromStr };
 
 use bon::bon;
 use itertools::Itertools;
@@ -37,7 +40,7 @@ pub type WList<V,W> = WeightedList<V,W>;
 ///     println!("{item}");
 /// }
 /// 
-/// if let Some(result) = wl.select_random_value(&mut rand::rng()) {
+/// if let Ok(result) = wl.select_random_value(&mut rand::rng()) {
 ///     println!("{result}");
 /// }
 /// ```
@@ -102,7 +105,7 @@ impl<V, W: Weight> WeightedList<V,W>
 
     /// Construct a [`WeightedList`] from an iterable of `value`s, merging duplicate values into single [`WeightedItem`]s.
     /// 
-    /// Note that this has $O(n^2)$ time complexity.
+    /// Note that this has $O(n^2)$ time complexity. If `V: Hash`, [`WeightedList::count_from()`] is much faster.
     pub fn from_expanded<I>(values: I) -> Self
         where
             I: IntoIterator<Item = V>,
@@ -121,6 +124,67 @@ impl<V, W: Weight> WeightedList<V,W>
 
         out
     }
+
+    /// Construct a [`WeightedList`] from an iterable of `value`s, counting how many times each value occurs. Items are ordered by the first occurrence of their value.
+    /// 
+    /// Equivalent to [`WeightedList::from_expanded()`], but uses hashing to achieve $O(n)$ time complexity.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = WeightedList::<_, u32>::count_from("abracadabra".chars());
+    /// 
+    /// assert_eq!( wl, wlist![(5, 'a'), (2, 'b'), (2, 'r'), (1, 'c'), (1, 'd')] );
+    /// ```
+    pub fn count_from<I>(values: I) -> Self
+        where
+            I: IntoIterator<Item = V>,
+            V: Eq + Hash,
+    {
+        let mut counts = HashMap::<V, (usize, W)>::new();
+
+        for value in values {
+            let next = counts.len();
+            counts.entry(value).or_insert((next, W::zero())).1 += W::one();
+        }
+
+        counts.into_iter()
+            .sorted_unstable_by_key(|(_, (order, _))| *order)
+            .map(|(value, (_, weight))| (weight, value))
+            .collect()
+    }
+
+    /// Construct a [`WeightedList`] by pairing up parallel iterables of `values` and `weights`.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = WeightedList::from_parallel(["sup", "nova", "shard"], [2, 3, 5]);
+    /// assert_eq!( wl.ok(), Some(wlist![(2, "sup"), (3, "nova"), (5, "shard")]) );
+    ///
+    /// let wl = WeightedList::from_parallel(["sup", "nova", "shard"], [2, 3]);
+    /// assert!( wl.is_err() );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LengthMismatch`] if `values` and `weights` have different lengths.
+    pub fn from_parallel<IV, IW>(values: IV, weights: IW) -> Result<Self, LengthMismatch>
+        where
+            IV: IntoIterator<Item = V>,
+            IW: IntoIterator<Item = W>,
+    {
+        let values = values.into_iter().collect_vec();
+        let weights = weights.into_iter().collect_vec();
+
+        if values.len() != weights.len() {
+            return Err(LengthMismatch { values: values.len(), weights: weights.len() });
+        }
+
+        Ok(weights.into_iter().zip(values).collect())
+    }
 }
 
 /// Construct a [`WeightedList`] from the provided `(weight, value)` pairs.
@@ -203,6 +267,30 @@ impl<V, W: Weight, const N: usize> From<[WeightedItem<V,W>; N]> for WeightedList
     }
 }
 
+/// Construct a [`WeightedList`] from a mapping of values to weights.
+///
+/// Since `HashMap` has no defined iteration order, the order of items in the list is arbitrary. Use a `BTreeMap` if you need a deterministic order.
+impl<V, W: Weight> From<HashMap<V,W>> for WeightedList<V,W> {
+    fn from(map: HashMap<V,W>) -> Self {
+        map.into_iter().map(|(value, weight)| (weight, value)).collect()
+    }
+}
+
+/// Construct a [`WeightedList`] from a mapping of values to weights, with items in ascending order of values.
+impl<V, W: Weight> From<BTreeMap<V,W>> for WeightedList<V,W> {
+    fn from(map: BTreeMap<V,W>) -> Self {
+        map.into_iter().map(|(value, weight)| (weight, value)).collect()
+    }
+}
+
+/// Construct a [`WeightedList`] from a mapping of values to weights, with items in the insertion order of the map.
+#[cfg(feature = "indexmap")]
+impl<V, W: Weight> From<indexmap::IndexMap<V,W>> for WeightedList<V,W> {
+    fn from(map: indexmap::IndexMap<V,W>) -> Self {
+        map.into_iter().map(|(value, weight)| (weight, value)).collect()
+    }
+}
+
 // == CONVERSIONS TO == //
 impl<V, W: Weight> From<WeightedList<V,W>> for Vec<WeightedItem<V,W>> {
     fn from(list: WeightedList<V,W>) -> Self {
@@ -245,6 +333,79 @@ impl<V, W: Weight> std::ops::DerefMut for WeightedList<V,W> {
     }
 }
 
+/// Methods for converting the list into other collections.
+impl<V, W: Weight> WeightedList<V,W>
+{
+    /// Consume the list and return a mapping of values to weights, merging the weights of any duplicate values.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// # use std::collections::HashMap;
+    /// let wl = wlist![(2, "sup"), (3, "nova"), (5, "sup")];
+    ///
+    /// assert_eq!( wl.into_map(), HashMap::from([("sup", 7), ("nova", 3)]) );
+    /// ```
+    pub fn into_map(self) -> HashMap<V,W>
+        where V: Eq + Hash
+    {
+        let mut out = HashMap::with_capacity(self.data.len());
+
+        for item in self.data {
+            *out.entry(item.value).or_insert(W::zero()) += item.weight;
+        }
+
+        out
+    }
+
+    /// Consume the list and return a mapping of values to weights, merging the weights of any duplicate values. Values are kept in order of their first occurrence.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova"), (5, "sup")];
+    /// let map = wl.into_index_map();
+    ///
+    /// assert_eq!( map.get("sup"), Some(&7) );
+    /// assert_eq!( map.keys().collect::<Vec<_>>(), [&"sup", &"nova"] );
+    /// ```
+    #[cfg(feature = "indexmap")]
+    pub fn into_index_map(self) -> indexmap::IndexMap<V,W>
+        where V: Eq + Hash
+    {
+        let mut out = indexmap::IndexMap::with_capacity(self.data.len());
+
+        for item in self.data {
+            *out.entry(item.value).or_insert(W::zero()) += item.weight;
+        }
+
+        out
+    }
+
+    /// Consume the list and split it into parallel vectors of weights and values.
+    ///
+    /// Inverse of [`WeightedList::from_parallel()`], though weights come first here for consistency with the rest of the crate.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    /// let (weights, values) = wl.unzip();
+    ///
+    /// assert_eq!( weights, vec![2, 3, 5] );
+    /// assert_eq!( values, vec!["sup", "nova", "shard"] );
+    /// ```
+    pub fn unzip(self) -> (Vec<W>, Vec<V>)
+    {
+        self.data.into_iter()
+            .map(|item| (item.weight, item.value))
+            .unzip()
+    }
+}
+
 // == TRAIT IMPLEMENTATIONS == //
 impl<V, W: Weight> Extend<WeightedItem<V,W>> for WeightedList<V,W>
 {
@@ -257,6 +418,30 @@ impl<V, W: Weight> Extend<WeightedItem<V,W>> for WeightedList<V,W>
     }
 }
 
+impl<V, W: Weight> Extend<(W,V)> for WeightedList<V,W>
+{
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item = (W,V)>
+    {
+        for (weight, value) in iter {
+            self.push_new_item(weight, value);
+        }
+    }
+}
+
+/// Concatenate an iterator of lists into a single list.
+impl<V, W: Weight> std::iter::Sum for WeightedList<V,W>
+{
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item = Self>
+    {
+        iter.fold(Self::new(), |acc, list| acc + list)
+    }
+}
+
+/// Write the list in its text format, with one item per line and weights aligned.
+///
+/// See the `FromStr` implementation for details of the format. Values are quoted if necessary, so that parsing the output gives back the same list.
 impl<V, W: Weight> Display for WeightedList<V,W>
     where
         V: Display,
@@ -264,17 +449,260 @@ impl<V, W: Weight> Display for WeightedList<V,W>
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
-        write!(f, "WeightedList[")?;
+        text::write_items(f, self.raw())
+    }
+}
+
+/// Parse a list from its text format, with one item per line.
+///
+/// Each line has a weight, followed by whitespace or `|`, then the value:
+///
+/// ```text
+/// // comments start with `//`
+/// 5  shard
+/// 2.5 | "multi word value"
+/// ```
+///
+/// - Blank lines and comments are ignored, including comments after a value.
+/// - Values are trimmed, and can be quoted to keep surrounding whitespace or `//`, with escapes `\"`, `\\`, `\n`, `\r` and `\t`.
+/// - Weights can have a `%` suffix, in which case they are read as the percentage itself. Either all or none of the weights must be percentages.
+///
+/// This is the inverse of the `Display` implementation.
+///
+/// # Usage
+///
+/// ```
+/// # use weighted_list::*;
+/// let wl: WeightedList<String, f32> = "
+///     // loot
+///     5  shard
+///     2.5 | \"multi word value\"
+/// ".parse()?;
+///
+/// assert_eq!( wl, wlist![(5.0, "shard".to_string()), (2.5, "multi word value".to_string())] );
+/// assert_eq!( wl.to_string().parse::<WeightedList<String, f32>>()?, wl );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns a [`ListParseError`] with the line and column of the first invalid item.
+impl<V, W: Weight> FromStr for WeightedList<V,W>
+    where
+        V: FromStr,
+        W: FromStr,
+{
+    type Err = ListParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err>
+    {
+        Ok(text::parse_items(source, false)?.into_iter().collect())
+    }
+}
+
+// == OPERATORS == //
+/// Concatenate two lists, appending the items of `other` to the end of `self`.
+///
+/// ```
+/// # use weighted_list::*;
+/// assert_eq!(
+///     wlist![(2, "sup")] + wlist![(3, "nova"), (5, "sup")],
+///     wlist![(2, "sup"), (3, "nova"), (5, "sup")]
+/// );
+/// ```
+impl<V, W: Weight> ops::Add for WeightedList<V,W>
+{
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self::Output {
+        self += other;
+        self
+    }
+}
+
+impl<V, W: Weight> ops::AddAssign for WeightedList<V,W>
+{
+    fn add_assign(&mut self, mut other: Self) {
+        self.append(&mut other);
+    }
+}
+
+/// Merge two lists, adding the weights of items in `other` to existing items in `self` with the same value. See [`WeightedList::merge_with()`] for details.
+///
+/// ```
+/// # use weighted_list::*;
+/// assert_eq!(
+///     wlist![(2, "sup")] | wlist![(3, "nova"), (5, "sup")],
+///     wlist![(7, "sup"), (3, "nova")]
+/// );
+/// ```
+impl<V, W: Weight> ops::BitOr for WeightedList<V,W>
+    where V: PartialEq
+{
+    type Output = Self;
+
+    fn bitor(mut self, other: Self) -> Self::Output {
+        self |= other;
+        self
+    }
+}
+
+impl<V, W: Weight> ops::BitOrAssign for WeightedList<V,W>
+    where V: PartialEq
+{
+    fn bitor_assign(&mut self, other: Self) {
+        self.merge_with(other);
+    }
+}
+
+/// Intersect two lists, keeping only values present in both. Each value keeps the minimum of its total weights between the lists.
+///
+/// Items keep their order from `self`. If `self` contains duplicate values, the weight available from `other` is used up by the earliest items first.
+///
+/// ```
+/// # use weighted_list::*;
+/// assert_eq!(
+///     wlist![(2, "sup"), (3, "nova")] & wlist![(5, "nova"), (1, "sup"), (7, "shard")],
+///     wlist![(1, "sup"), (3, "nova")]
+/// );
+/// ```
+impl<V, W: Weight> ops::BitAnd for WeightedList<V,W>
+    where V: PartialEq
+{
+    type Output = Self;
+
+    fn bitand(mut self, other: Self) -> Self::Output {
+        self &= other;
+        self
+    }
+}
+
+impl<V, W: Weight> ops::BitAndAssign for WeightedList<V,W>
+    where V: PartialEq
+{
+    fn bitand_assign(&mut self, mut other: Self) {
+        other.merge_duplicates();
 
-        if !self.data.is_empty() {
-            write!(f, "\n")?;
+        for item in &mut self.data {
+            match other.data.iter_mut().find(|each| each.value == item.value) {
+                Some(budget) => {
+                    if budget.weight < item.weight {
+                        item.weight = budget.weight;
+                    }
+                    budget.weight -= item.weight;
+                },
+                None => item.weight = W::zero(),
+            }
         }
 
-        for item in &self.data {
-            writeln!(f, "  {item},")?;
+        self.prune();
+    }
+}
+
+/// Subtract the weights of items in `other` from items in `self` with the same value, then [prune](WeightedList::prune) any items left with non-positive weight.
+///
+/// If `self` contains duplicate values, weight is subtracted from the earliest items first.
+///
+/// ```
+/// # use weighted_list::*;
+/// assert_eq!(
+///     wlist![(2, "sup"), (3, "nova"), (5, "shard")] - wlist![(1, "nova"), (5, "shard"), (7, "qi")],
+///     wlist![(2, "sup"), (2, "nova")]
+/// );
+/// ```
+impl<V, W: Weight> ops::Sub for WeightedList<V,W>
+    where V: PartialEq
+{
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self::Output {
+        self -= other;
+        self
+    }
+}
+
+impl<V, W: Weight> ops::SubAssign for WeightedList<V,W>
+    where V: PartialEq
+{
+    fn sub_assign(&mut self, other: Self) {
+        for item in other {
+            let mut remaining = item.weight;
+
+            for existing in self.data.iter_mut().filter(|each| each.value == item.value)
+            {
+                if remaining <= W::zero() { break }
+
+                if remaining >= existing.weight {
+                    remaining -= existing.weight;
+                    existing.weight = W::zero();
+                } else {
+                    existing.weight -= remaining;
+                    remaining = W::zero();
+                }
+            }
+        }
+
+        self.prune();
+    }
+}
+
+/// Scale the weights of all items by `scalar`.
+///
+/// ```
+/// # use weighted_list::*;
+/// assert_eq!( wlist![(2, "sup"), (3, "nova")] * 2, wlist![(4, "sup"), (6, "nova")] );
+/// ```
+///
+/// # Panics
+///
+/// Panics if any weight overflows, in both debug and release builds. Use [`WeightedList::checked_mul()`] to handle overflow instead.
+impl<V, W: Weight> ops::Mul<W> for WeightedList<V,W>
+{
+    type Output = Self;
+
+    fn mul(mut self, scalar: W) -> Self::Output {
+        self *= scalar;
+        self
+    }
+}
+
+impl<V, W: Weight> ops::MulAssign<W> for WeightedList<V,W>
+{
+    fn mul_assign(&mut self, scalar: W) {
+        for item in &mut self.data {
+            item.weight = util::checked_mul(item.weight, scalar).unwrap_or_else(|err| panic!("{err}"));
         }
+    }
+}
+
+/// Divide the weights of all items by `scalar`.
+///
+/// For integer weights, the division truncates, so items lighter than `scalar` are left with a weight of `0`. These are not removed; call [`WeightedList::prune()`] afterwards to drop them.
+///
+/// ```
+/// # use weighted_list::*;
+/// assert_eq!( wlist![(2.0, "sup"), (3.0, "nova")] / 2.0, wlist![(1.0, "sup"), (1.5, "nova")] );
+///
+/// let wl = wlist![(1, "sup"), (3, "nova")] / 2;
+/// assert_eq!( wl, wlist![(0, "sup"), (1, "nova")] );
+/// assert_eq!( wl.pruned(), wlist![(1, "nova")] );
+/// ```
+impl<V, W: Weight> ops::Div<W> for WeightedList<V,W>
+{
+    type Output = Self;
+
+    fn div(mut self, scalar: W) -> Self::Output {
+        self /= scalar;
+        self
+    }
+}
 
-        write!(f, "]")
+impl<V, W: Weight> ops::DivAssign<W> for WeightedList<V,W>
+{
+    fn div_assign(&mut self, scalar: W) {
+        for item in &mut self.data {
+            item.weight /= scalar;
+        }
     }
 }
 
@@ -518,6 +946,81 @@ impl<V, W: Weight> WeightedList<V,W>
     {
         self.values().collect_vec()
     }
+
+    /// Get a vector of (weight, value) tuples representing each item in the list.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova")];
+    ///
+    /// assert_eq!(wl.collect_raw(), vec![(2, &"sup"), (3, &"nova")]);
+    /// ```
+    pub fn collect_raw(&self) -> Vec<(W,&V)>
+    {
+        self.raw().collect_vec()
+    }
+
+    /// Consume the list and return a vector of (weight, value) tuples representing each item.
+    ///
+    /// Owned variant of [`.collect_raw()`](Self::collect_raw).
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova")];
+    ///
+    /// assert_eq!(wl.into_raw(), vec![(2, "sup"), (3, "nova")]);
+    /// ```
+    pub fn into_raw(self) -> Vec<(W,V)>
+    {
+        self.data.into_iter().map(Into::into).collect_vec()
+    }
+
+    /// Get an iterator over the values of each item in the list, skipping any value equal to one already yielded.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova"), (5, "sup")];
+    ///
+    /// let unique = wl.unique_values().collect::<Vec<_>>();
+    /// assert_eq!(unique, vec![&"sup", &"nova"]);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Each value is checked against every preceding item, so fully consuming the iterator takes $O(n^2)$ time. If `V: Hash`, [`.unique_values_hashed()`](Self::unique_values_hashed) is faster.
+    pub fn unique_values(&self) -> impl Iterator<Item = &V>
+        where V: Eq
+    {
+        self.data.iter()
+            .enumerate()
+            .filter(|(i, item)| !self.data[..*i].iter().any(|prev| prev.value == item.value))
+            .map(|(_, item)| &item.value)
+    }
+
+    /// Get an iterator over the values of each item in the list, skipping any value equal to one already yielded.
+    ///
+    /// Variant of [`.unique_values()`](Self::unique_values) which tracks seen values in a `HashSet`, taking $O(n)$ time to fully consume.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova"), (5, "sup")];
+    ///
+    /// let unique = wl.unique_values_hashed().collect::<Vec<_>>();
+    /// assert_eq!(unique, vec![&"sup", &"nova"]);
+    /// ```
+    pub fn unique_values_hashed(&self) -> impl Iterator<Item = &V>
+        where V: Eq + Hash
+    {
+        self.values().unique()
+    }
 }
 
 // == PROPERTIES == //
@@ -531,7 +1034,8 @@ impl<V, W: Weight> WeightedList<V,W>
     /// - `self.len() == 0` does not imply the list is empty – items may have zero or negative weights! To check if the list is empty, use [`.is_empty()`](Self::is_empty) instead.
     pub fn len(&self) -> W
     {
-        self.data.iter().map(|item| item.weight).sum()
+        // fold rather than sum, since float sums start from `-0.0`
+        self.data.iter().fold(W::zero(), |total, item| total + item.weight)
     }
 
     pub fn capacity(&self) -> usize
@@ -644,13 +1148,68 @@ impl<V, W: Weight> WeightedList<V,W>
     }
 }
 
-// == LIST MUTATION == //
-/// Methods specialised from `Vec<>` for mutating the list.
+// == SLICING == //
+/// Methods for taking *weighted* slices of the list.
 impl<V, W: Weight> WeightedList<V,W>
 {
-    pub fn reserve(&mut self, additional: usize) -> &mut Self
-    {
-        self.data.reserve(additional);
+    /// Return a clone of the items covering the weighted span `range`. Items that straddle either boundary of the span have their weight trimmed to the overlap.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    ///
+    /// assert_eq!(
+    ///     wl.weighted_slice(1..7),
+    ///     wlist![(1, "sup"), (3, "nova"), (2, "shard")]
+    /// );
+    /// assert_eq!( wl.weighted_slice(..=2), wlist![(2, "sup"), (1, "nova")] );
+    /// assert_eq!( wl.weighted_slice(8..), wlist![(2, "shard")] );
+    /// assert_eq!( wl.weighted_slice(..), wl );
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - For integer weights, bounds follow [weighted indexing](WeightedList#indexing), so `..=b` covers up to and including the weighted index `b`, i.e. the span `..b+1`.
+    /// - For float weights, inclusive and exclusive bounds cover the same span, so `..=b` is equivalent to `..b`.
+    /// - Items with no overlap (including any with zero weight) are excluded.
+    /// - An empty or reversed span gives an empty list.
+    pub fn weighted_slice<R>(&self, range: R) -> Self
+        where
+            R: RangeBounds<W>,
+            V: Clone,
+    {
+        self._weighted_slice_(range)
+            .map(|(weight, value)| (weight, value.clone()))
+            .collect()
+    }
+
+    /// Borrowed variant of [`.weighted_slice()`](Self::weighted_slice), returning a list of references to the original values instead of cloning them.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup".to_string()), (3, "nova".to_string())];
+    /// let view = wl.weighted_slice_ref(1..3);
+    ///
+    /// assert_eq!( view, wlist![(1, &wl[0].value), (1, &wl[2].value)] );
+    /// ```
+    pub fn weighted_slice_ref<R>(&self, range: R) -> WeightedList<&V, W>
+        where R: RangeBounds<W>
+    {
+        self._weighted_slice_(range).collect()
+    }
+}
+
+// == LIST MUTATION == //
+/// Methods specialised from `Vec<>` for mutating the list.
+impl<V, W: Weight> WeightedList<V,W>
+{
+    pub fn reserve(&mut self, additional: usize) -> &mut Self
+    {
+        self.data.reserve(additional);
         self
     }
 
@@ -875,6 +1434,128 @@ impl<V, W: Weight> WeightedList<V,W>
         self.data.iter().any(|item| item.value == *value)
     }
 
+    /// How many items in the list have a weight equal to `weight`?
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova"), (2, "shard")];
+    ///
+    /// assert_eq!( wl.count_with_weight(2), 2 );
+    /// assert_eq!( wl.count_with_weight(7), 0 );
+    /// ```
+    pub fn count_with_weight(&self, weight: W) -> usize
+    {
+        self.data.iter().filter(|item| item.weight == weight).count()
+    }
+
+    /// Find the unweighted index of the first item with a value equal to `value`, or `None` if there is no such item.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    ///
+    /// assert_eq!( wl.position_of(&"nova"), Some(1) );
+    /// assert_eq!( wl.position_of(&"qi"), None );
+    /// ```
+    pub fn position_of(&self, value: &V) -> Option<usize>
+        where V: PartialEq
+    {
+        self.nth_position_of(value, 0)
+    }
+
+    /// Find the unweighted index of the `n`th (counting from `0`) item with a value equal to `value`, or `None` if there are not enough such items.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova"), (5, "sup")];
+    ///
+    /// assert_eq!( wl.nth_position_of(&"sup", 0), Some(0) );
+    /// assert_eq!( wl.nth_position_of(&"sup", 1), Some(2) );
+    /// assert_eq!( wl.nth_position_of(&"sup", 2), None );
+    /// ```
+    pub fn nth_position_of(&self, value: &V, n: usize) -> Option<usize>
+        where V: PartialEq
+    {
+        self.data.iter()
+            .positions(|item| item.value == *value)
+            .nth(n)
+    }
+
+    /// Find the weighted index at which the first item with a value equal to `value` starts, or `None` if there is no such item.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    ///
+    /// assert_eq!( wl.weighted_position_of(&"sup"), Some(0) );
+    /// assert_eq!( wl.weighted_position_of(&"nova"), Some(2) );
+    /// assert_eq!( wl.weighted_position_of(&"shard"), Some(5) );
+    /// assert_eq!( wl.weighted_position_of(&"qi"), None );
+    /// ```
+    pub fn weighted_position_of(&self, value: &V) -> Option<W>
+        where V: PartialEq
+    {
+        self.find_all_where(|item| item.value == *value)
+            .next()
+            .map(|(span, _)| span.start)
+    }
+
+    /// Find the first item that fulfils `predicate`, or `None` if there is no such item.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    ///
+    /// assert_eq!( wl.find_where(|item| item.weight > 2), Some(&wit!(3, "nova")) );
+    /// assert_eq!( wl.find_where(|item| item.weight > 5), None );
+    /// ```
+    pub fn find_where<F>(&self, mut predicate: F) -> Option<&WeightedItem<V,W>>
+        where F: FnMut(&WeightedItem<V,W>) -> bool
+    {
+        self.data.iter().find(|item| predicate(item))
+    }
+
+    /// Get an iterator over all items that fulfil `predicate`, each paired with the span of weighted indices it covers.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    ///
+    /// let found = wl.find_all_where(|item| item.weight > 2).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(found, vec![
+    ///     (2..5,  &wit!(3, "nova")),
+    ///     (5..10, &wit!(5, "shard")),
+    /// ]);
+    /// ```
+    pub fn find_all_where<F>(&self,
+        mut predicate: F
+    ) -> impl Iterator<Item = (Range<W>, &WeightedItem<V,W>)>
+        where F: FnMut(&WeightedItem<V,W>) -> bool
+    {
+        let mut t = W::zero();
+
+        self.data.iter()
+            .filter_map(move |item| {
+                let start = t;
+                t += item.weight;
+
+                predicate(item).then_some((start..t, item))
+            })
+    }
+
     /// Compute a weighted sum over the list.
     /// 
     /// For each item in the list, apply `value_map` to its value, multiply the result by its weight, and return the sum of the results.
@@ -890,7 +1571,7 @@ impl<V, W: Weight> WeightedList<V,W>
     /// let mut wl = wlist![(2, 100), (3, 10), (5, 1)];
     /// 
     /// // 2×100 + 3×10 + 5×1
-    /// let weighted_sum = wl.weighted_sum(|v| *v);
+    /// let weighted_sum = wl.weighted_sum::<i32>(|v| *v);
     /// 
     /// assert_eq!(235, weighted_sum);
     /// ```
@@ -927,6 +1608,37 @@ impl<V, W: Weight> WeightedList<V,W>
                 .weighted_sum(value_map)
         )
     }
+
+    /// Compute the Shannon entropy of the list in bits, treating each item as an outcome with probability proportional to its weight.
+    /// 
+    /// Items with non-positive weight are ignored, so the entropy of a list with no positive weights is `0.0`. Items with duplicate values are treated as separate outcomes, so you may wish to [`merge_duplicates()`](Self::merge_duplicates) first.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let coin = wlist![(1, "heads"), (1, "tails")];
+    /// assert_eq!( coin.entropy()?, 1.0 );
+    /// 
+    /// let dice = wlist![(1, 1), (1, 2), (1, 3), (1, 4)];
+    /// assert_eq!( dice.entropy()?, 2.0 );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn entropy(&self) -> Result<f64, NumCastFailure>
+    {
+        let weights = self.data.iter()
+            .filter(|item| item.weight > W::zero())
+            .map(|item| util::try_cast::<W, f64>(item.weight))
+            .collect::<Result<Vec<f64>, _>>()?;
+
+        let total = weights.iter().sum::<f64>();
+
+        Ok(
+            weights.into_iter()
+                .map(|weight| weight / total)
+                .fold(0.0, |entropy, prob| entropy - prob * prob.log2())
+        )
+    }
 }
 
 // == SPECIALISED MUTATION == //
@@ -951,6 +1663,33 @@ impl<V, W: Weight> WeightedList<V,W>
         out
     }
 
+    /// Scale the weights of all items by `scalar`, returning an error rather than panicking if any weight overflows.
+    /// 
+    /// Checked version of the `*` operator.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2_u8, "sup"), (100, "nova")];
+    /// 
+    /// assert_eq!( wl.clone().checked_mul(2)?, wlist![(4, "sup"), (200, "nova")] );
+    /// assert!( wl.checked_mul(3).is_err() );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    /// 
+    /// # Errors
+    /// 
+    /// Returns [`WeightOverflow`] if any weight overflows `W`.
+    pub fn checked_mul(mut self, scalar: W) -> Result<Self, WeightOverflow>
+    {
+        for item in &mut self.data {
+            item.weight = util::checked_mul(item.weight, scalar)?;
+        }
+
+        Ok(self)
+    }
+
     /// Find the first occurrence (from the left) of an item with `value`, and remove the entire item.
     /// 
     /// # Usage
@@ -1041,252 +1780,1040 @@ impl<V, W: Weight> WeightedList<V,W>
     /// 
     /// ```
     /// # use weighted_list::*;
-    /// let mut wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    /// let mut wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    /// 
+    /// assert_eq!(
+    ///     *wl.zero_all_weights(),
+    ///     wlist![(0, "sup"), (0, "nova"), (0, "shard")],
+    /// )
+    /// ```
+    pub fn zero_all_weights(&mut self) -> &mut Self
+    {
+        for item in &mut self.data {
+            item.weight = W::zero();
+        }
+
+        self
+    }
+
+    /// Set the weight of all items to `weight`.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    /// 
+    /// assert_eq!(
+    ///     *wl.set_all_weights(1),
+    ///     wlist![(1, "sup"), (1, "nova"), (1, "shard")],
+    /// )
+    /// ```
+    pub fn set_all_weights(&mut self, weight: W) -> &mut Self
+    {
+        for item in &mut self.data {
+            item.weight = weight;
+        }
+
+        self
+    }
+
+    /// Return a clone of the list with all item weights normalised such that they sum to `1.0`.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    /// 
+    /// assert_eq!(
+    ///     wl.normalised().ok(),
+    ///     Some(wlist![(0.2, "sup"), (0.3, "nova"), (0.5, "shard")])
+    /// );
+    /// ```
+    pub fn normalised(&self) -> Result<WeightedList<V, f64>, NumCastFailure>
+        where V: Clone
+    {
+        let l = self.len();
+
+        let total = util::try_cast::<W, f64>(l)?;
+
+        let items = self.data.iter()
+            .map(|item| {
+                let weight = util::try_cast::<W, f64>(item.weight)?;
+
+                Ok(WeightedItem {
+                    weight: weight / total,
+                    value: item.value.clone()
+                })
+            })
+            .collect::<Result<_, _>>();
+
+        items.map(|data| WeightedList { data })
+    }
+}
+
+/// Methods for merging items into the list.
+/// 
+/// This involves comparing item values to check for duplicates, hence requiring `V: PartialEq`.
+impl<V, W: Weight> WeightedList<V,W>
+    where
+        V: PartialEq
+{
+    /// Merge an item into the list. If an item with the same value already exists, add the weight of the new item to the existing item. Otherwise, append the new item to the list.
+    /// 
+    /// # Tips
+    /// 
+    /// - Use this method when you already have an existing [`WeightedItem`] instance. If you're going to construct a new [`WeightedItem`], [`.merge_new_item()`](Self::merge_new_item) is probably more convenient.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut wl = wlist![(1, "sup")];
+    /// 
+    /// let item = WeightedItem::new(2, "sup");
+    /// wl.merge_item(item);
+    /// assert!(wl == wlist![(3, "sup")]);
+    /// // "sup" merged with existing instance
+    /// 
+    /// let item = WeightedItem::unit("elysion");
+    /// wl.merge_item(item);
+    /// assert!(wl == wlist![(3, "sup"), (1, "elysion")]);
+    /// // "elysion" appended to end
+    /// ```
+    pub fn merge_item(&mut self, item: WeightedItem<V,W>) -> &mut Self
+    {
+        if let Some(existing) = self.data.iter_mut().find(|each| each.value == item.value) {
+            existing.weight += item.weight;
+        }
+        else {
+            self.data.push(item);
+        }
+
+        self
+    }
+
+    /// Merge a new item with `value` and `weight` into the list.
+    /// 
+    /// See [`.merge_item()`](Self::merge_item) for details.
+    pub fn merge_new_item(&mut self, weight: W, value: V) -> &mut Self
+    {
+        self.merge_item(WeightedItem { weight, value })
+    }
+
+    /// Merge a new item with `value` and a weight of `1` into the list.
+    /// 
+    /// See [`.merge_item()`](Self::merge_item) for details.
+    pub fn merge_value(&mut self, value: V) -> &mut Self
+    {
+        self.merge_item(WeightedItem::unit(value))
+    }
+
+    /// Merge the items of `other` into `self`, leaving `other` empty.
+    /// 
+    /// See [`.merge_item()`](Self::merge_item) for details.
+    pub fn merge_with(&mut self, other: WeightedList<V,W>) -> &mut Self
+    {
+        for item in other {
+            self.merge_item(item);
+        }
+
+        self
+    }
+
+    /// Merge any items in the list with duplicate values by combining their weights with the first instance.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut wl = wlist![
+    ///     (1, "sup"),
+    ///     (2, ""),
+    ///     (20, "sup")
+    /// ];
+    /// 
+    /// assert_eq!(
+    ///     *wl.merge_duplicates(),
+    ///     wlist![
+    ///         (21, "sup"),
+    ///         (2, ""),
+    ///     ]
+    /// );
+    /// ```
+    pub fn merge_duplicates(&mut self) -> &mut Self
+    {
+        let orig = std::mem::replace(self, WeightedList::new());
+        self.merge_with(orig);
+        self
+    }
+}
+
+/// Methods for taking items from the list.
+/// 
+/// This involves returning values without removing the existing one, hence requiring `V: Clone`.
+impl<V, W: Weight> WeightedList<V,W>
+    where
+        V: Clone
+{
+    /// Decrement the weight of the item at `weighted_index` by `1`. If its weight becomes non-positive as a result, remove the entire item. Returns a clone of the item with its updated weight.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    /// 
+    /// wl.take_one_at(2);
+    /// assert_eq!( wl, wlist![(2, "sup"), (2, "nova"), (5, "shard")] );
+    /// 
+    /// wl.take_one_at(2);
+    /// assert_eq!( wl, wlist![(2, "sup"), (1, "nova"), (5, "shard")] );
+    /// 
+    /// wl.take_one_at(2);
+    /// assert_eq!( wl, wlist![(2, "sup"), (5, "shard")] );
+    /// ```
+    pub fn take_one_at(&mut self, weighted_index: W) -> WeightedItem<V,W>
+    {
+        self.take_by_at(W::one(), weighted_index)
+    }
+
+    /// Decrement the weight of the item at `weighted_index` by `decrement`. If its weight becomes non-positive as a result, remove the entire item. Returns a clone of the item with its updated weight.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `weighted_index` is out of bounds.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    /// 
+    /// wl.take_by_at(2, 2);
+    /// assert_eq!( wl, wlist![(2, "sup"), (1, "nova"), (5, "shard")] );
+    /// 
+    /// wl.take_by_at(2, 2);
+    /// assert_eq!( wl, wlist![(2, "sup"), (5, "shard")]);
+    /// ```
+    pub fn take_by_at(&mut self, decrement: W, weighted_index: W) -> WeightedItem<V,W>
+    {
+        let idx = self._unweight_index_(weighted_index);
+        let target = &mut self.data[idx];
+
+        if decrement >= target.weight {
+            target.weight = W::zero();
+            self.data.remove(idx)
+        }
+        else {
+            target.weight -= decrement;
+            target.clone()
+        }
+    }
+
+    /// Remove the entire item at `weighted_index`.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    /// 
+    /// wl.take_entire_at(3);
+    /// assert_eq!( wl, wlist![(2, "sup"), (5, "shard")] );
+    /// ```
+    pub fn take_entire_at(&mut self, weighted_index: W) -> WeightedItem<V,W>
+    {
+        self.remove_at(weighted_index)
+    }
+}
+
+// == TRANSFORMS == //
+/// Methods for transforming the values of items, preserving their weights and order.
+impl<V, W: Weight> WeightedList<V,W>
+{
+    /// Apply `map` to the value of each item (in-place).
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut wl = wlist![(2, "sup"), (3, "nova")];
+    /// 
+    /// assert_eq!( *wl.map_values(|v| if *v == "sup" { "qi" } else { v }), wlist![(2, "qi"), (3, "nova")] );
+    /// ```
+    pub fn map_values(&mut self, mut map: impl FnMut(&V) -> V) -> &mut Self
+    {
+        for item in &mut self.data {
+            item.value = map(&item.value);
+        }
+
+        self
+    }
+
+    /// Return a new list with `map` applied to the value of each item.
+    /// 
+    /// Out-of-place version of [`.map_values()`](Self::map_values), which can also change the type of values.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova")];
+    /// 
+    /// assert_eq!( wl.mapped_values(|v| v.len()), wlist![(2, 3), (3, 4)] );
+    /// ```
+    pub fn mapped_values<U>(&self, mut map: impl FnMut(&V) -> U) -> WeightedList<U,W>
+    {
+        self.data.iter()
+            .map(|item| (item.weight, map(&item.value)))
+            .collect()
+    }
+
+    /// Apply the fallible `map` to the value of each item (in-place).
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut wl = wlist![(2, 2_u8), (3, 3)];
+    /// 
+    /// assert!( wl.try_map_values(|v| v.checked_mul(50).ok_or(*v)).is_ok() );
+    /// assert_eq!( wl, wlist![(2, 100), (3, 150)] );
+    /// 
+    /// assert_eq!( wl.try_map_values(|v| v.checked_mul(2).ok_or(*v)).unwrap_err(), vec![150] );
+    /// assert_eq!( wl, wlist![(2, 100), (3, 150)] );
+    /// ```
+    /// 
+    /// # Errors
+    /// 
+    /// If `map` fails for any items, returns the errors for all of them in order, and leaves the list unchanged.
+    pub fn try_map_values<E>(&mut self,
+        mut map: impl FnMut(&V) -> Result<V, E>
+    ) -> Result<&mut Self, Vec<E>>
+    {
+        let (values, errors): (Vec<_>, Vec<_>) =
+            self.data.iter()
+                .map(|item| map(&item.value))
+                .partition_result();
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        for (item, value) in self.data.iter_mut().zip(values) {
+            item.value = value;
+        }
+
+        Ok(self)
+    }
+
+    /// Return a new list with the fallible `map` applied to the value of each item.
+    /// 
+    /// Out-of-place version of [`.try_map_values()`](Self::try_map_values), which can also change the type of values.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "2"), (3, "3")];
+    /// assert_eq!( wl.try_mapped_values(|v| v.parse::<u8>()), Ok(wlist![(2, 2), (3, 3)]) );
+    /// 
+    /// let wl = wlist![(2, "2"), (3, "x"), (5, "y")];
+    /// assert_eq!( wl.try_mapped_values(|v| v.parse::<u8>()).unwrap_err().len(), 2 );
+    /// ```
+    /// 
+    /// # Errors
+    /// 
+    /// If `map` fails for any items, returns the errors for all of them in order, rather than stopping at the first.
+    pub fn try_mapped_values<U, E>(&self,
+        mut map: impl FnMut(&V) -> Result<U, E>
+    ) -> Result<WeightedList<U,W>, Vec<E>>
+    {
+        let (data, errors): (Vec<_>, Vec<_>) =
+            self.data.iter()
+                .map(|item| map(&item.value).map(|value| WeightedItem::new(item.weight, value)))
+                .partition_result();
+
+        if errors.is_empty() {
+            Ok(WeightedList { data })
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Apply `map` to the value of each item, removing items for which it returns `None` (in-place).
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    /// 
+    /// assert_eq!(
+    ///     *wl.filter_map_values(|v| (*v != "nova").then(|| &v[1..])),
+    ///     wlist![(2, "up"), (5, "hard")]
+    /// );
+    /// ```
+    pub fn filter_map_values(&mut self, mut map: impl FnMut(&V) -> Option<V>) -> &mut Self
+    {
+        self.data.retain_mut(|item| {
+            match map(&item.value) {
+                Some(value) => { item.value = value; true },
+                None        => false,
+            }
+        });
+
+        self
+    }
+
+    /// Return a new list with `map` applied to the value of each item, excluding items for which it returns `None`.
+    /// 
+    /// Out-of-place version of [`.filter_map_values()`](Self::filter_map_values), which can also change the type of values.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "2"), (3, "x"), (5, "5")];
+    /// 
+    /// assert_eq!( wl.filter_mapped_values(|v| v.parse::<u8>().ok()), wlist![(2, 2), (5, 5)] );
+    /// ```
+    pub fn filter_mapped_values<U>(&self, mut map: impl FnMut(&V) -> Option<U>) -> WeightedList<U,W>
+    {
+        self.data.iter()
+            .filter_map(|item| map(&item.value).map(|value| (item.weight, value)))
+            .collect()
+    }
+
+    /// Apply `map` to each item, replacing it with the new `(weight, value)` pair (in-place).
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut wl = wlist![(2, "sup"), (3, "nova")];
+    /// 
+    /// assert_eq!(
+    ///     *wl.map_items(|item| (item.weight * 2, item.value)),
+    ///     wlist![(4, "sup"), (6, "nova")]
+    /// );
+    /// ```
+    pub fn map_items(&mut self, mut map: impl FnMut(&WeightedItem<V,W>) -> (W, V)) -> &mut Self
+    {
+        for item in &mut self.data {
+            (item.weight, item.value) = map(item);
+        }
+
+        self
+    }
+
+    /// Return a new list with `map` applied to each item, giving the new `(weight, value)` pair.
+    /// 
+    /// Out-of-place version of [`.map_items()`](Self::map_items), which can also change the type of values.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (3, "nova")];
+    /// 
+    /// assert_eq!(
+    ///     wl.mapped_items(|item| (item.weight * 2, item.value.len())),
+    ///     wlist![(4, 3), (6, 4)]
+    /// );
+    /// ```
+    pub fn mapped_items<U>(&self, map: impl FnMut(&WeightedItem<V,W>) -> (W, U)) -> WeightedList<U,W>
+    {
+        self.data.iter().map(map).collect()
+    }
+
+    /// Replace the value of every item with a value equal to `old` with a clone of `new` (in-place).
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut wl = wlist![(2, "sup"), (3, "nova"), (5, "sup")];
+    /// 
+    /// assert_eq!(
+    ///     *wl.replace_value(&"sup", "shard"),
+    ///     wlist![(2, "shard"), (3, "nova"), (5, "shard")]
+    /// );
+    /// ```
+    pub fn replace_value(&mut self, old: &V, new: V) -> &mut Self
+        where V: Clone + PartialEq
+    {
+        for item in self.data.iter_mut().filter(|item| item.value == *old) {
+            item.value = new.clone();
+        }
+
+        self
+    }
+
+    /// Return a clone of the list with the value of every item with a value equal to `old` replaced with a clone of `new`.
+    /// 
+    /// Out-of-place version of [`.replace_value()`](Self::replace_value).
+    pub fn replaced_value(&self, old: &V, new: V) -> Self
+        where V: Clone + PartialEq
+    {
+        let mut out = self.clone();
+        out.replace_value(old, new);
+        out
+    }
+}
+
+// == GROUPING == //
+/// Methods for grouping items by a key derived from their values.
+impl<V, W: Weight> WeightedList<V,W>
+{
+    /// Group items by the key `key_fn` derives from their value, summing the weights of each group into a single item. Items are ordered by the first occurrence of their key.
+    /// 
+    /// This gives the marginal distribution over keys, if the list is a joint distribution over values.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![
+    ///     (2, ("forest", "wolf")),
+    ///     (3, ("desert", "husk")),
+    ///     (5, ("forest", "spider")),
+    /// ];
+    /// 
+    /// assert_eq!(
+    ///     wl.group_by_key(|(biome, _)| *biome),
+    ///     wlist![(7, "forest"), (3, "desert")]
+    /// );
+    /// ```
+    pub fn group_by_key<K>(&self, mut key_fn: impl FnMut(&V) -> K) -> WeightedList<K,W>
+        where K: Eq + Hash
+    {
+        let mut groups = HashMap::<K, (usize, W)>::new();
+
+        for item in &self.data {
+            let next = groups.len();
+            groups.entry(key_fn(&item.value)).or_insert((next, W::zero())).1 += item.weight;
+        }
+
+        groups.into_iter()
+            .sorted_unstable_by_key(|(_, (order, _))| *order)
+            .map(|(key, (_, weight))| (weight, key))
+            .collect()
+    }
+
+    /// Partition the items into separate lists by the key `key_fn` derives from their value. Each list preserves the order of its items.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![
+    ///     (2, ("forest", "wolf")),
+    ///     (3, ("desert", "husk")),
+    ///     (5, ("forest", "spider")),
+    /// ];
+    /// 
+    /// let parts = wl.partition_by(|(biome, _)| *biome);
+    /// 
+    /// assert_eq!( parts.len(), 2 );
+    /// assert_eq!( parts["forest"], wlist![(2, ("forest", "wolf")), (5, ("forest", "spider"))] );
+    /// assert_eq!( parts["desert"], wlist![(3, ("desert", "husk"))] );
+    /// ```
+    pub fn partition_by<K>(&self, mut key_fn: impl FnMut(&V) -> K) -> HashMap<K, WeightedList<V,W>>
+        where
+            V: Clone,
+            K: Eq + Hash,
+    {
+        let mut out = HashMap::<K, WeightedList<V,W>>::new();
+
+        for item in &self.data {
+            out.entry(key_fn(&item.value))
+                .or_insert_with(WeightedList::new)
+                .push_item(item.clone());
+        }
+
+        out
+    }
+
+    /// Return the normalised distribution of items whose value has `key`, as derived by `key_fn`.
+    /// 
+    /// If no items have `key`, returns an empty list.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![
+    ///     (2, ("forest", "wolf")),
+    ///     (3, ("desert", "husk")),
+    ///     (6, ("forest", "spider")),
+    /// ];
+    /// 
+    /// assert_eq!(
+    ///     wl.conditional_on(&"forest", |(biome, _)| *biome)?,
+    ///     wlist![(0.25, ("forest", "wolf")), (0.75, ("forest", "spider"))]
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn conditional_on<K>(&self,
+        key: &K,
+        mut key_fn: impl FnMut(&V) -> K,
+    ) -> Result<WeightedList<V, f64>, NumCastFailure>
+        where
+            V: Clone,
+            K: PartialEq,
+    {
+        self.data.iter()
+            .filter(|item| key_fn(&item.value) == *key)
+            .cloned()
+            .collect::<WeightedList<V,W>>()
+            .normalised()
+    }
+}
+
+// == PRODUCTS == //
+/// Methods for combining lists into joint distributions.
+/// 
+/// Weights are multiplied exactly, so these methods return a [`WeightOverflow`] error if a weight exceeds the range of `W`.
+impl<V, W: Weight> WeightedList<V,W>
+{
+    /// Return the Cartesian product of `self` and `other`, pairing every value of `self` with every value of `other` and multiplying their weights.
+    /// 
+    /// This gives the joint distribution of independently selecting from both lists. Items are ordered by `self` first, then `other`.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let weapons = wlist![(2, "sword"), (3, "bow")];
+    /// let elements = wlist![(1, "fire"), (4, "ice")];
+    /// 
+    /// assert_eq!(
+    ///     weapons.product(&elements)?,
+    ///     wlist![
+    ///         (2, ("sword", "fire")),
+    ///         (8, ("sword", "ice")),
+    ///         (3, ("bow", "fire")),
+    ///         (12, ("bow", "ice")),
+    ///     ]
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn product<U>(&self, other: &WeightedList<U,W>) -> Result<WeightedList<(V,U), W>, WeightOverflow>
+        where
+            V: Clone,
+            U: Clone,
+    {
+        self.data.iter()
+            .cartesian_product(other.data.iter())
+            .map(|(lhs, rhs)| Ok((
+                util::checked_mul(lhs.weight, rhs.weight)?,
+                (lhs.value.clone(), rhs.value.clone()),
+            )))
+            .collect()
+    }
+
+    /// Return the Cartesian product of all `lists`, with each combination of values collected into a `Vec` in the order of `lists`.
+    /// 
+    /// The product of no lists is a single empty combination with a weight of `1`.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let coin = wlist![(1, 'H'), (1, 'T')];
+    /// let flips = WeightedList::product_all([&coin, &coin, &coin])?;
+    /// 
+    /// assert_eq!( flips.total_items(), 8 );
+    /// assert_eq!( flips[0].value, vec!['H', 'H', 'H'] );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn product_all<'l>(
+        lists: impl IntoIterator<Item = &'l WeightedList<V,W>>
+    ) -> Result<WeightedList<Vec<V>, W>, WeightOverflow>
+        where
+            V: Clone + 'l,
+            W: 'l,
+    {
+        let mut out = wlist![(W::one(), Vec::new())];
+
+        for list in lists {
+            out = out.data.iter()
+                .cartesian_product(list.data.iter())
+                .map(|(combo, item)| Ok((
+                    util::checked_mul(combo.weight, item.weight)?,
+                    combo.value.iter().chain([&item.value]).cloned().collect(),
+                )))
+                .collect::<Result<_,_>>()?;
+        }
+
+        Ok(out)
+    }
+
+    /// Return the Cartesian product of `self` and `other`, combining each pair of values with `zip` and merging items with equal results.
+    /// 
+    /// Items are ordered by the first occurrence of their result.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let coin = wlist![(1, 0), (1, 1)];
+    /// 
+    /// assert_eq!(
+    ///     coin.zip_with(&coin, |a, b| a + b)?,
+    ///     wlist![(1, 0), (2, 1), (1, 2)]
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    /// 
+    /// # Notes
+    /// 
+    /// - Merging only requires `R: PartialEq`, so this takes $O(nmk)$ time for $k$ distinct results.
+    pub fn zip_with<U, R>(&self,
+        other: &WeightedList<U,W>,
+        mut zip: impl FnMut(&V, &U) -> R,
+    ) -> Result<WeightedList<R,W>, WeightOverflow>
+        where R: PartialEq
+    {
+        let mut out = WeightedList::<R,W>::new();
+
+        for (lhs, rhs) in self.data.iter().cartesian_product(other.data.iter()) {
+            let weight = util::checked_mul(lhs.weight, rhs.weight)?;
+            let value = zip(&lhs.value, &rhs.value);
+
+            if let Some(existing) = out.data.iter_mut().find(|each| each.value == value) {
+                existing.weight = util::checked_add(existing.weight, weight)?;
+            }
+            else {
+                out.push_new_item(weight, value);
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Return the distribution of the sum of independently selecting a value from `self` and `other`, merging items with equal sums.
+    /// 
+    /// Equivalent to [`.zip_with()`](Self::zip_with) with addition.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// # use weighted_list::*;
+    /// let d4 = WeightedList::<i32, u32>::from_expanded(1..=4);
     /// 
     /// assert_eq!(
-    ///     *wl.zero_all_weights(),
-    ///     wlist![(0, "sup"), (0, "nova"), (0, "shard")],
-    /// )
+    ///     d4.convolve(&d4)?,
+    ///     wlist![(1, 2), (2, 3), (3, 4), (4, 5), (3, 6), (2, 7), (1, 8)]
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn zero_all_weights(&mut self) -> &mut Self
+    pub fn convolve(&self, other: &WeightedList<V,W>) -> Result<WeightedList<V,W>, WeightOverflow>
+        where V: Clone + PartialEq + ops::Add<Output = V>
     {
-        for item in &mut self.data {
-            item.weight = W::zero();
-        }
-
-        self
+        self.zip_with(other, |lhs, rhs| lhs.clone() + rhs.clone())
     }
+}
 
-    /// Set the weight of all items to `weight`.
+// == COMPOSITION == //
+/// Methods for composing distributions, where each value leads to a further distribution.
+impl<V, W: Weight> WeightedList<V,W>
+{
+    /// Map each value to a list with `map`, then combine the lists into a single list. Each inner list is normalised, then scaled by the weight of its outer item, so the result is the distribution of selecting an outer item, then selecting an inner item from its list.
+    /// 
+    /// Items are ordered by their outer item, then their position in the inner list.
     /// 
     /// # Usage
     /// 
     /// ```
     /// # use weighted_list::*;
-    /// let mut wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    /// let regions = wlist![(3, "forest"), (1, "desert")];
     /// 
-    /// assert_eq!(
-    ///     *wl.set_all_weights(1),
-    ///     wlist![(1, "sup"), (1, "nova"), (1, "shard")],
-    /// )
+    /// let mobs = regions.flat_map(|region| match *region {
+    ///     "forest" => wlist![(1, "wolf"), (2, "spider")],
+    ///     _        => wlist![(5, "husk")],
+    /// })?;
+    /// 
+    /// assert_eq!( mobs, wlist![(1.0, "wolf"), (2.0, "spider"), (1.0, "husk")] );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn set_all_weights(&mut self, weight: W) -> &mut Self
+    /// 
+    /// # Notes
+    /// 
+    /// - Inner lists with a total weight of zero are skipped, since they cannot be normalised.
+    /// - The result has the same total weight as `self`, minus the weights of any skipped items.
+    pub fn flat_map<U>(&self,
+        mut map: impl FnMut(&V) -> WeightedList<U,W>
+    ) -> Result<WeightedList<U, f64>, NumCastFailure>
     {
-        for item in &mut self.data {
-            item.weight = weight;
+        let mut out = WeightedList::new();
+
+        for item in &self.data {
+            let inner = map(&item.value);
+            let inner_total = util::try_cast::<W, f64>(inner.len())?;
+
+            if inner_total <= 0.0 { continue }
+
+            let outer = util::try_cast::<W, f64>(item.weight)?;
+
+            for each in inner {
+                let weight = util::try_cast::<W, f64>(each.weight)?;
+                out.push_new_item(outer * weight / inner_total, each.value);
+            }
         }
 
-        self
+        Ok(out)
     }
+}
 
-    /// Return a clone of the list with all item weights normalised such that they sum to `1.0`.
+/// Methods for lists of lists.
+impl<V, W: Weight> WeightedList<WeightedList<V,W>, W>
+{
+    /// Flatten a list of lists into a single list. Each inner list is normalised, then scaled by the weight of its outer item.
+    /// 
+    /// Equivalent to [`.flat_map()`](Self::flat_map) with the identity function. To select from the inner lists without flattening them, use a [`Mixture`].
     /// 
     /// # Usage
     /// 
     /// ```
     /// # use weighted_list::*;
-    /// let mut wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    /// let nested = wlist![
+    ///     (2, wlist![(1, "sup"), (1, "nova")]),
+    ///     (4, wlist![(3, "shard")]),
+    /// ];
     /// 
-    /// assert_eq!(
-    ///     wl.normalised().ok(),
-    ///     Some(wlist![(0.2, "sup"), (0.3, "nova"), (0.5, "shard")])
-    /// );
+    /// assert_eq!( nested.flatten()?, wlist![(1.0, "sup"), (1.0, "nova"), (4.0, "shard")] );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn normalised(&self) -> Result<WeightedList<V, f64>, NumCastFailure>
+    pub fn flatten(&self) -> Result<WeightedList<V, f64>, NumCastFailure>
         where V: Clone
     {
-        let l = self.len();
-
-        let total = util::try_cast::<W, f64>(l)?;
-
-        let items = self.data.iter()
-            .map(|item| {
-                let weight = util::try_cast::<W, f64>(item.weight)?;
-
-                Ok(WeightedItem {
-                    weight: weight / total,
-                    value: item.value.clone()
-                })
-            })
-            .collect::<Result<_, _>>();
-
-        items.map(|data| WeightedList { data })
+        self.flat_map(WeightedList::clone)
     }
 }
 
-/// Methods for merging items into the list.
+// == INFERENCE == //
+/// Methods for Bayesian inference, treating the list as a distribution of beliefs over hypotheses.
 /// 
-/// This involves comparing item values to check for duplicates, hence requiring `V: PartialEq`.
-impl<V, W: Weight> WeightedList<V,W>
-    where
-        V: PartialEq
+/// These require `W = f64`, since posterior weights are generally not integral.
+impl<V> WeightedList<V, f64>
 {
-    /// Merge an item into the list. If an item with the same value already exists, add the weight of the new item to the existing item. Otherwise, append the new item to the list.
-    /// 
-    /// # Tips
+    /// Update the list as a prior distribution over hypotheses, multiplying the weight of each item by the `likelihood` of the evidence given its value, then normalising the weights to sum to `1.0`.
     /// 
-    /// - Use this method when you already have an existing [`WeightedItem`] instance. If you're going to construct a new [`WeightedItem`], [`.merge_new_item()`](Self::merge_new_item) is probably more convenient.
+    /// Returns the model evidence, the probability of the evidence under the prior, which is the normalising constant of the posterior.
     /// 
     /// # Usage
     /// 
     /// ```
     /// # use weighted_list::*;
-    /// let mut wl = wlist![(1, "sup")];
+    /// let mut coins = wlist![(0.5, "fair"), (0.5, "biased")];
     /// 
-    /// let item = WeightedItem::new(2, "sup");
-    /// wl.merge_item(item);
-    /// assert!(wl == wlist![(3, "sup")]);
-    /// // "sup" merged with existing instance
+    /// // observe heads
+    /// let evidence = coins.bayes_update(|coin| if *coin == "fair" { 0.5 } else { 0.9 })?;
     /// 
-    /// let item = WeightedItem::unit("elysion");
-    /// wl.merge_item(item);
-    /// assert!(wl == wlist![(3, "sup"), (1, "elysion")]);
-    /// // "elysion" appended to end
+    /// assert_eq!( evidence, 0.7 );
+    /// assert_eq!( coins, wlist![(0.25 / 0.7, "fair"), (0.45 / 0.7, "biased")] );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn merge_item(&mut self, item: WeightedItem<V,W>) -> &mut Self
+    /// 
+    /// # Errors
+    /// 
+    /// If every posterior weight is zero, or any weight or likelihood is negative or `NaN`, returns [`ZeroEvidence`] and leaves the list unchanged.
+    /// 
+    /// # Notes
+    /// 
+    /// - The prior does not need to be normalised.
+    pub fn bayes_update(&mut self, mut likelihood: impl FnMut(&V) -> f64) -> Result<f64, ZeroEvidence>
     {
-        if let Some(existing) = self.data.iter_mut().find(|each| each.value == item.value) {
-            existing.weight += item.weight;
-        }
-        else {
-            self.data.push(item);
-        }
+        let prior_total = self.len();
 
-        self
-    }
+        let posterior = self.data.iter()
+            .map(|item| item.weight * likelihood(&item.value))
+            .collect::<Vec<_>>();
 
-    /// Merge a new item with `value` and `weight` into the list.
-    /// 
-    /// See [`.merge_item()`](Self::merge_item) for details.
-    pub fn merge_new_item(&mut self, weight: W, value: V) -> &mut Self
-    {
-        self.merge_item(WeightedItem { weight, value })
-    }
+        let posterior_total = posterior.iter().sum::<f64>();
 
-    /// Merge a new item with `value` and a weight of `1` into the list.
-    /// 
-    /// See [`.merge_item()`](Self::merge_item) for details.
-    pub fn merge_value(&mut self, value: V) -> &mut Self
-    {
-        self.merge_item(WeightedItem::unit(value))
-    }
+        if posterior.iter().any(|weight| weight.is_nan() || *weight < 0.0) || posterior_total <= 0.0 {
+            return Err(ZeroEvidence);
+        }
 
-    /// Merge the items of `other` into `self`, leaving `other` empty.
-    /// 
-    /// See [`.merge_item()`](Self::merge_item) for details.
-    pub fn merge_with(&mut self, other: WeightedList<V,W>) -> &mut Self
-    {
-        for item in other {
-            self.merge_item(item);
+        for (item, weight) in self.data.iter_mut().zip(posterior) {
+            item.weight = weight / posterior_total;
         }
 
-        self
+        Ok(posterior_total / prior_total)
     }
 
-    /// Merge any items in the list with duplicate values by combining their weights with the first instance.
+    /// Update the list as a prior distribution over hypotheses with each of `observations` in turn, where `likelihood` gives the likelihood of an observation given the value of an item. The weights are then normalised to sum to `1.0`.
+    /// 
+    /// Equivalent to calling [`.bayes_update()`](Self::bayes_update) for each observation, but accumulates weights in log-space to avoid underflow over long sequences of observations.
+    /// 
+    /// Returns the natural logarithm of the model evidence, the joint probability of all the observations under the prior.
     /// 
     /// # Usage
     /// 
     /// ```
     /// # use weighted_list::*;
-    /// let mut wl = wlist![
-    ///     (1, "sup"),
-    ///     (2, ""),
-    ///     (20, "sup")
-    /// ];
+    /// let mut coins = wlist![(0.5, "fair"), (0.5, "biased")];
     /// 
-    /// assert_eq!(
-    ///     *wl.merge_duplicates(),
-    ///     wlist![
-    ///         (21, "sup"),
-    ///         (2, ""),
-    ///     ]
-    /// );
+    /// let likelihood = |coin: &&str, heads: &bool| match (*coin, heads) {
+    ///     ("fair", _) => 0.5,
+    ///     (_, true) => 0.9,
+    ///     (_, false) => 0.1,
+    /// };
+    /// 
+    /// // 2000 flips, underflowing if multiplied directly
+    /// let flips = [true, false].repeat(1000);
+    /// let log_evidence = coins.sequential_update(flips, likelihood)?;
+    /// 
+    /// assert!( log_evidence.is_finite() );
+    /// assert!( coins.weights().next().unwrap() > 0.999 );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn merge_duplicates(&mut self) -> &mut Self
+    /// 
+    /// # Errors
+    /// 
+    /// If every posterior weight is zero, or any weight or likelihood is negative or `NaN`, returns [`ZeroEvidence`] and leaves the list unchanged.
+    pub fn sequential_update<O>(&mut self,
+        observations: impl IntoIterator<Item = O>,
+        mut likelihood: impl FnMut(&V, &O) -> f64,
+    ) -> Result<f64, ZeroEvidence>
     {
-        let orig = std::mem::replace(self, WeightedList::new());
-        self.merge_with(orig);
-        self
+        let prior_total = self.len();
+
+        // `ln()` turns negative values into `NaN`, which would otherwise be silently ignored
+        let checked_ln = |value: f64| if value >= 0.0 { Ok(value.ln()) } else { Err(ZeroEvidence) };
+
+        let mut log_weights = self.data.iter()
+            .map(|item| checked_ln(item.weight))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for observation in observations {
+            for (item, log_weight) in self.data.iter().zip(&mut log_weights) {
+                *log_weight += checked_ln(likelihood(&item.value, &observation))?;
+            }
+        }
+
+        let max = log_weights.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        if !max.is_finite() {
+            return Err(ZeroEvidence);
+        }
+
+        let scaled_total = log_weights.iter()
+            .map(|log_weight| (log_weight - max).exp())
+            .sum::<f64>();
+
+        for (item, log_weight) in self.data.iter_mut().zip(log_weights) {
+            item.weight = (log_weight - max).exp() / scaled_total;
+        }
+
+        Ok(max + scaled_total.ln() - prior_total.ln())
     }
 }
 
-/// Methods for taking items from the list.
+// == RANKING == //
+/// Methods for ranking items by weight.
 /// 
-/// This involves returning values without removing the existing one, hence requiring `V: Clone`.
+/// These operate on individual items, so if the list contains duplicate values, you may wish to [merge](Self::merge_duplicates) them first. Items with equal weights are ranked in order of their position in the list.
 impl<V, W: Weight> WeightedList<V,W>
-    where
-        V: Clone
 {
-    /// Decrement the weight of the item at `weighted_index` by `1`. If its weight becomes non-positive as a result, remove the entire item. Returns a clone of the item with its updated weight.
+    /// Get the `k` items with the greatest weights, in descending order of weight.
+    /// 
+    /// Uses a bounded heap, taking $O(n \log k)$ time.
     /// 
     /// # Usage
     /// 
     /// ```
     /// # use weighted_list::*;
-    /// let mut wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    /// let wl = wlist![(2, "sup"), (5, "shard"), (3, "nova")];
     /// 
-    /// wl.take_one_at(2);
-    /// assert_eq!( wl, wlist![(2, "sup"), (2, "nova"), (5, "shard")] );
+    /// assert_eq!( wl.most_common(2), vec![&wit!(5, "shard"), &wit!(3, "nova")] );
+    /// ```
+    pub fn most_common(&self, k: usize) -> Vec<&WeightedItem<V,W>>
+    {
+        self._extreme_k_indices_::<true>(k)
+            .into_iter()
+            .map(|idx| &self.data[idx])
+            .collect()
+    }
+
+    /// Get the `k` items with the least weights, in ascending order of weight.
     /// 
-    /// wl.take_one_at(2);
-    /// assert_eq!( wl, wlist![(2, "sup"), (1, "nova"), (5, "shard")] );
+    /// Uses a bounded heap, taking $O(n \log k)$ time.
+    /// 
+    /// # Usage
     /// 
-    /// wl.take_one_at(2);
-    /// assert_eq!( wl, wlist![(2, "sup"), (5, "shard")] );
     /// ```
-    pub fn take_one_at(&mut self, weighted_index: W) -> WeightedItem<V,W>
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (5, "shard"), (3, "nova")];
+    /// 
+    /// assert_eq!( wl.least_common(2), vec![&wit!(2, "sup"), &wit!(3, "nova")] );
+    /// ```
+    pub fn least_common(&self, k: usize) -> Vec<&WeightedItem<V,W>>
     {
-        self.take_by_at(W::one(), weighted_index)
+        self._extreme_k_indices_::<false>(k)
+            .into_iter()
+            .map(|idx| &self.data[idx])
+            .collect()
     }
 
-    /// Decrement the weight of the item at `weighted_index` by `decrement`. If its weight becomes non-positive as a result, remove the entire item. Returns a clone of the item with its updated weight.
+    /// Return a clone of the list with only the `k` items with the greatest weights, keeping their original order.
     /// 
-    /// # Panics
+    /// # Usage
     /// 
-    /// Panics if `weighted_index` is out of bounds.
+    /// ```
+    /// # use weighted_list::*;
+    /// let wl = wlist![(2, "sup"), (5, "shard"), (3, "nova")];
+    /// 
+    /// assert_eq!( wl.top_k_by_weight(2), wlist![(5, "shard"), (3, "nova")] );
+    /// ```
+    pub fn top_k_by_weight(&self, k: usize) -> Self
+        where V: Clone
+    {
+        self._extreme_k_indices_::<true>(k)
+            .into_iter()
+            .sorted_unstable()
+            .map(|idx| self.data[idx].clone())
+            .collect()
+    }
+
+    /// Remove the item with the greatest weight and return it, or `None` if the list is empty.
     /// 
     /// # Usage
     /// 
     /// ```
     /// # use weighted_list::*;
-    /// let mut wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
-    /// 
-    /// wl.take_by_at(2, 2);
-    /// assert_eq!( wl, wlist![(2, "sup"), (1, "nova"), (5, "shard")] );
+    /// let mut wl = wlist![(2, "sup"), (5, "shard"), (3, "nova")];
     /// 
-    /// wl.take_by_at(2, 2);
-    /// assert_eq!( wl, wlist![(2, "sup"), (5, "shard")]);
+    /// assert_eq!( wl.pop_max_weight(), Some(wit!(5, "shard")) );
+    /// assert_eq!( wl.pop_max_weight(), Some(wit!(3, "nova")) );
+    /// assert_eq!( wl, wlist![(2, "sup")] );
     /// ```
-    pub fn take_by_at(&mut self, decrement: W, weighted_index: W) -> WeightedItem<V,W>
+    pub fn pop_max_weight(&mut self) -> Option<WeightedItem<V,W>>
     {
-        let idx = self._unweight_index_(weighted_index);
-        let target = &mut self.data[idx];
-
-        if decrement >= target.weight {
-            target.weight = W::zero();
-            self.data.remove(idx)
-        }
-        else {
-            target.weight -= decrement;
-            target.clone()
-        }
+        let idx = self._extreme_k_indices_::<true>(1).pop()?;
+        Some(self.data.remove(idx))
     }
 
-    /// Remove the entire item at `weighted_index`.
+    /// Remove the item with the least weight and return it, or `None` if the list is empty.
     /// 
     /// # Usage
     /// 
     /// ```
     /// # use weighted_list::*;
-    /// let mut wl = wlist![(2, "sup"), (3, "nova"), (5, "shard")];
+    /// let mut wl = wlist![(2, "sup"), (5, "shard"), (3, "nova")];
     /// 
-    /// wl.take_entire_at(3);
-    /// assert_eq!( wl, wlist![(2, "sup"), (5, "shard")] );
+    /// assert_eq!( wl.pop_min_weight(), Some(wit!(2, "sup")) );
+    /// assert_eq!( wl, wlist![(5, "shard"), (3, "nova")] );
     /// ```
-    pub fn take_entire_at(&mut self, weighted_index: W) -> WeightedItem<V,W>
+    pub fn pop_min_weight(&mut self) -> Option<WeightedItem<V,W>>
     {
-        self.remove_at(weighted_index)
+        let idx = self._extreme_k_indices_::<false>(1).pop()?;
+        Some(self.data.remove(idx))
     }
 }
 
@@ -1294,7 +2821,7 @@ impl<V, W: Weight> WeightedList<V,W>
 /// Methods for out-of-place random sampling from a list.
 impl<V, W: Weight> WeightedList<V,W>
 {
-    fn _get_random_weighted_index_up_to_<RNG>(&self,
+    pub(crate) fn _get_random_weighted_index_up_to_<RNG>(&self,
         rng: &mut RNG,
         upper: W,
     ) -> Result<W, NumCastFailure>
@@ -1302,12 +2829,15 @@ impl<V, W: Weight> WeightedList<V,W>
     {
         let len:    f64 = util::try_cast::<W, f64>(upper)?;
         let scalar: f64 = rng.random();
-        let idx:    f64 = (len * scalar).floor();
+        let idx:    f64 = len * scalar;
 
-        util::try_cast::<f64, W>(idx)
+        // only integer weights need a whole index, since float weights can subdivide it
+        util::try_cast::<f64, W>(
+            if util::is_integral::<W>() { idx.floor() } else { idx }
+        )
     }
 
-    fn _get_random_weighted_index_<RNG>(&self, rng: &mut RNG) -> Result<W, NumCastFailure>
+    pub(crate) fn _get_random_weighted_index_<RNG>(&self, rng: &mut RNG) -> Result<W, NumCastFailure>
         where RNG: Rng + ?Sized
     {
         self._get_random_weighted_index_up_to_(rng, self.len())
@@ -1813,11 +3343,15 @@ impl<V, W: Weight> WeightedList<V,W>
     /// 
     /// println!("{wl}");
     /// // could give:
-    /// //   WeightedList[{3, sup}, {5, nova}, {2, shard}]
+    /// //   3  sup
+    /// //   5  nova
+    /// //   2  shard
     /// 
     /// println!("{wl}");
     /// // could now give:
-    /// //   WeightedList[{2, sup}, {5, nova}, {3, shard}]
+    /// //   2  sup
+    /// //   5  nova
+    /// //   3  shard
     /// ```
     pub fn shuffle_weights<RNG>(&mut self, rng: &mut RNG) -> &mut Self
         where RNG: Rng + ?Sized
@@ -1869,7 +3403,7 @@ impl<V, W: Weight> WeightedList<V,W>
     }
 
     /// Convert a `weighted_index` to its unweighted equivalent in the underlying `Vec<>`. Panics on overflow.
-    fn _unweight_index_(&self, weighted_index: W) -> usize
+    pub(crate) fn _unweight_index_(&self, weighted_index: W) -> usize
     {
         let mut t = W::zero();
 
@@ -1908,8 +3442,105 @@ impl<V, W: Weight> WeightedList<V,W>
 
         None
     }
+
+    /// Find the unweighted indices of the `k` heaviest (if `HEAVIEST`) or lightest items, ordered from most to least extreme, using a bounded heap.
+    fn _extreme_k_indices_<const HEAVIEST: bool>(&self, k: usize) -> Vec<usize>
+    {
+        if k == 0 { return vec![] }
+
+        let mut heap = BinaryHeap::<Reverse<RankedIndex<W, HEAVIEST>>>::with_capacity(k + 1);
+
+        for (idx, item) in self.data.iter().enumerate() {
+            heap.push(Reverse(RankedIndex { weight: item.weight, idx }));
+
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(entry)| entry.idx)
+            .collect()
+    }
+
+    /// Iterate over the `(weight, value)` pairs overlapping the weighted span `range`, with weights trimmed to the overlap.
+    ///
+    /// Bounds are compared against the cumulative boundaries of each item, so that integer bounds are only stepped past within an item, which cannot overflow.
+    fn _weighted_slice_<R>(&self, range: R) -> impl Iterator<Item = (W, &V)>
+        where R: RangeBounds<W>
+    {
+        let start = range.start_bound().cloned();
+        let end = range.end_bound().cloned();
+
+        /* NOTE: Integer weights index whole units, so excluding or including index `b` steps to `b+1`, whereas float weights are continuous */
+        let is_integral = util::is_integral::<W>();
+        let mut t = W::zero();
+
+        self.data.iter()
+            .filter_map(move |item| {
+                let lower = t;
+                t += item.weight;
+                let upper = t;
+
+                let lower = match start {
+                    Bound::Unbounded => lower,
+                    Bound::Included(bound) => if bound > lower { bound } else { lower },
+                    Bound::Excluded(bound) if is_integral => {
+                        if bound < lower { lower } else if bound < upper { bound + W::one() } else { upper }
+                    },
+                    Bound::Excluded(bound) => if bound > lower { bound } else { lower },
+                };
+
+                let upper = match end {
+                    Bound::Unbounded => upper,
+                    Bound::Included(bound) if is_integral => {
+                        if bound < lower { lower } else if bound < upper { bound + W::one() } else { upper }
+                    },
+                    Bound::Included(bound) | Bound::Excluded(bound) => if bound < upper { bound } else { upper },
+                };
+
+                (upper > lower).then(|| (upper - lower, &item.value))
+            })
+    }
+}
+
+
+/// An index into a [`WeightedList`] ranked by the weight of its item, for use in a `BinaryHeap`. Greater entries are heavier (if `HEAVIEST`) or lighter items, with ties broken in favour of earlier items.
+struct RankedIndex<W, const HEAVIEST: bool>
+{
+    weight: W,
+    idx: usize,
+}
+
+impl<W: Weight, const HEAVIEST: bool> Ord for RankedIndex<W, HEAVIEST>
+{
+    fn cmp(&self, other: &Self) -> Ordering
+    {
+        /* NOTE: Incomparable weights (e.g. NaN) are treated as equal, so the heap remains well-behaved */
+        let by_weight = self.weight.partial_cmp(&other.weight).unwrap_or(Ordering::Equal);
+        let by_weight = if HEAVIEST { by_weight } else { by_weight.reverse() };
+
+        by_weight.then(other.idx.cmp(&self.idx))
+    }
+}
+
+impl<W: Weight, const HEAVIEST: bool> PartialOrd for RankedIndex<W, HEAVIEST>
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Weight, const HEAVIEST: bool> PartialEq for RankedIndex<W, HEAVIEST>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
 }
 
+impl<W: Weight, const HEAVIEST: bool> Eq for RankedIndex<W, HEAVIEST> {}
+
 
 #[cfg(test)] mod tests
 {
@@ -1962,6 +3593,17 @@ impl<V, W: Weight> WeightedList<V,W>
         assert_eq!( list._unweight_index_nopanic_(12), 3 );
     }
 
+    #[test] fn _extreme_k_indices_()
+    {
+        let list = wlist![(2, "sup"), (5, "shard"), (3, "nova"), (5, "cortex")];
+
+        assert_eq!( list._extreme_k_indices_::<true>(0), Vec::<usize>::new() );
+        assert_eq!( list._extreme_k_indices_::<true>(2), vec![1, 3] );
+        assert_eq!( list._extreme_k_indices_::<true>(10), vec![1, 3, 2, 0] );
+        assert_eq!( list._extreme_k_indices_::<false>(2), vec![0, 2] );
+        assert_eq!( list._extreme_k_indices_::<false>(10), vec![0, 2, 1, 3] );
+    }
+
     #[test] fn _unweight_index_skipping_()
     {
         let list = wl();
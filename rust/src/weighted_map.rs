@@ -0,0 +1,746 @@
+use std::collections::{ HashMap };
+use std::error::{ Error };
+use std::fmt::{ Debug, Display };
+use std::hash::{ Hash };
+
+use bon::bon;
+use rand::prelude::*;
+
+use crate::*;
+use crate::errors::*;
+
+
+/// A shorthand for [`WeightedMap`].
+///
+/// If you refer to [`WeightedMap`] prolifically in your code, you may wish to use this for brevity. Otherwise, the full [`WeightedMap`] is recommended for clarity.
+pub type WMap<V,W> = WeightedMap<V,W>;
+
+
+/// A [`WeightedList`] with unique values, indexed by a `HashMap` for $O(1)$ lookup of items by value.
+///
+/// Merging, querying and updating weights by value are all $O(1)$, compared to $O(n)$ for a [`WeightedList`]. This makes it suitable for building frequency tables from large numbers of observations.
+///
+/// # Usage
+///
+/// ```
+/// # use weighted_list::*;
+/// let mut wm = WeightedMap::new();
+///
+/// for word in "the cat sat on the mat".split(' ') {
+///     wm.merge_value(word);
+/// }
+///
+/// assert_eq!( wm.weight_of(&"the"), Some(2) );
+/// assert_eq!( wm.weight_of(&"cat"), Some(1) );
+/// assert_eq!( wm.total_items(), 5 );
+///
+/// if let Ok(word) = wm.select_random_value(&mut rand::rng()) {
+///     println!("{word}");
+/// }
+/// ```
+///
+/// # Ordering
+///
+/// Items are kept in insertion order, and support [weighted indexing](WeightedList#indexing) just like a [`WeightedList`]. Removing an item shifts all later items down (like [`Vec::remove()`]) to preserve their order, so removal is $O(n)$.
+///
+/// # Notes
+///
+/// - Each value is stored twice (once in the list, once as a key in the index), hence most methods require `V: Clone`.
+#[derive(Clone, Debug)]
+pub struct WeightedMap<V, W: Weight>
+{
+    list: WeightedList<V,W>,
+    indices: HashMap<V, usize>,
+}
+
+// == CONSTRUCTORS == //
+/// Methods for constructing a [`WeightedMap`].
+impl<V, W: Weight> WeightedMap<V,W>
+{
+    /// Construct an empty map.
+    pub fn new() -> Self
+    {
+        Self { list: WeightedList::new(), indices: HashMap::new() }
+    }
+
+    /// Construct an empty map with the specified capacity.
+    pub fn with_capacity(capacity: usize) -> Self
+    {
+        Self {
+            list: WeightedList::with_capacity(capacity),
+            indices: HashMap::with_capacity(capacity),
+        }
+    }
+}
+
+impl<V, W: Weight> WeightedMap<V,W>
+    where
+        V: Clone + Eq + Hash
+{
+    /// Construct a [`WeightedMap`] from an iterable of `value`s, counting how many times each value occurs.
+    ///
+    /// Unlike [`WeightedList::from_expanded()`], this has $O(n)$ time complexity.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wm = WeightedMap::<_, u32>::from_expanded(["sup", "nova", "sup"]);
+    ///
+    /// assert_eq!( wm.weight_of(&"sup"), Some(2) );
+    /// assert_eq!( wm.weight_of(&"nova"), Some(1) );
+    /// ```
+    pub fn from_expanded<I>(values: I) -> Self
+        where I: IntoIterator<Item = V>
+    {
+        let mut out = Self::new();
+
+        for value in values {
+            out.merge_value(value);
+        }
+
+        out
+    }
+}
+
+// == CONVERSIONS == //
+impl<V, W: Weight> FromIterator<(W,V)> for WeightedMap<V,W>
+    where
+        V: Clone + Eq + Hash
+{
+    /// Collect `(weight, value)` pairs into a map, merging the weights of duplicate values.
+    fn from_iter<I>(pairs: I) -> Self
+        where I: IntoIterator<Item = (W,V)>
+    {
+        let mut out = Self::new();
+
+        for (weight, value) in pairs {
+            out.merge_new_item(weight, value);
+        }
+
+        out
+    }
+}
+
+impl<V, W: Weight> FromIterator<WeightedItem<V,W>> for WeightedMap<V,W>
+    where
+        V: Clone + Eq + Hash
+{
+    /// Collect items into a map, merging the weights of duplicate values.
+    fn from_iter<I>(items: I) -> Self
+        where I: IntoIterator<Item = WeightedItem<V,W>>
+    {
+        let mut out = Self::new();
+
+        for item in items {
+            out.merge_item(item);
+        }
+
+        out
+    }
+}
+
+/// Convert a [`WeightedList`] into a [`WeightedMap`], merging the weights of duplicate values into their first occurrence. This is $O(n)$.
+impl<V, W: Weight> From<WeightedList<V,W>> for WeightedMap<V,W>
+    where
+        V: Clone + Eq + Hash
+{
+    fn from(list: WeightedList<V,W>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+/// Convert a [`WeightedMap`] into a [`WeightedList`]. This is $O(1)$, since the items are already stored in a list.
+impl<V, W: Weight> From<WeightedMap<V,W>> for WeightedList<V,W>
+{
+    fn from(map: WeightedMap<V,W>) -> Self {
+        map.list
+    }
+}
+
+impl<V, W: Weight> AsRef<WeightedList<V,W>> for WeightedMap<V,W>
+{
+    fn as_ref(&self) -> &WeightedList<V,W> {
+        &self.list
+    }
+}
+
+impl<V, W: Weight> std::ops::Deref for WeightedMap<V,W>
+{
+    type Target = [WeightedItem<V,W>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.list
+    }
+}
+
+// == TRAIT IMPLEMENTATIONS == //
+impl<V, W: Weight> Default for WeightedMap<V,W>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Two maps are equal if they contain equal items in the same order.
+impl<V, W: Weight> PartialEq for WeightedMap<V,W>
+    where
+        V: PartialEq
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.list == other.list
+    }
+}
+
+impl<V, W: Weight> Eq for WeightedMap<V,W>
+    where
+        V: Eq
+{}
+
+impl<V, W: Weight> Extend<(W,V)> for WeightedMap<V,W>
+    where
+        V: Clone + Eq + Hash
+{
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item = (W,V)>
+    {
+        for (weight, value) in iter {
+            self.merge_new_item(weight, value);
+        }
+    }
+}
+
+impl<V, W: Weight> Display for WeightedMap<V,W>
+    where
+        V: Display,
+        W: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "WeightedMap[")?;
+
+        if !self.list.is_empty() {
+            writeln!(f)?;
+        }
+
+        for item in &self.list {
+            writeln!(f, "  {item},")?;
+        }
+
+        write!(f, "]")
+    }
+}
+
+// == INDEXING == //
+impl<V, W: Weight> std::ops::Index<W> for WeightedMap<V,W>
+{
+    type Output = WeightedItem<V,W>;
+
+    fn index(&self, weighted_index: W) -> &Self::Output
+    {
+        &self.list[weighted_index]
+    }
+}
+
+// == ITERATION == //
+impl<V, W: Weight> IntoIterator for WeightedMap<V,W>
+{
+    type Item = WeightedItem<V,W>;
+    type IntoIter = <WeightedList<V,W> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+impl<'l, V, W: Weight> IntoIterator for &'l WeightedMap<V,W>
+{
+    type Item = &'l WeightedItem<V,W>;
+    type IntoIter = std::slice::Iter<'l, WeightedItem<V,W>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.iter()
+    }
+}
+
+// == ACCESSORS == //
+/// Methods for accessing data of the map.
+impl<V, W: Weight> WeightedMap<V,W>
+{
+    /// Get an iterator over the weights of each item in the map.
+    pub fn weights(&self) -> impl Iterator<Item = W>
+    {
+        self.list.weights()
+    }
+
+    /// Get an iterator over the values of each item in the map.
+    pub fn values(&self) -> impl Iterator<Item = &V>
+    {
+        self.list.values()
+    }
+
+    /// Get an iterator over (weight, value) tuples representing each item in the map.
+    pub fn raw(&self) -> impl Iterator<Item = (W,&V)>
+    {
+        self.list.raw()
+    }
+
+    /// Borrow the items of the map as a [`WeightedList`].
+    pub fn as_list(&self) -> &WeightedList<V,W>
+    {
+        &self.list
+    }
+
+    /// Consume the map and return its items as a [`WeightedList`]. This is $O(1)$.
+    pub fn into_list(self) -> WeightedList<V,W>
+    {
+        self.list
+    }
+}
+
+// == PROPERTIES == //
+/// Methods for computing properties of the map.
+impl<V, W: Weight> WeightedMap<V,W>
+{
+    /// Sum the weights of all items in the map. See [`WeightedList::len()`].
+    pub fn len(&self) -> W
+    {
+        self.list.len()
+    }
+
+    /// How many items/values are in the map?
+    pub fn total_items(&self) -> usize
+    {
+        self.list.total_items()
+    }
+
+    /// Does the map contain no items?
+    pub fn is_empty(&self) -> bool
+    {
+        self.list.is_empty()
+    }
+
+    /// Do all items have a weight of `0`? See [`WeightedList::is_zero()`].
+    pub fn is_zero(&self) -> bool
+    {
+        self.list.is_zero()
+    }
+}
+
+// == QUERYING & MUTATION == //
+/// Methods for querying and updating items by value, in $O(1)$ time except where noted.
+impl<V, W: Weight> WeightedMap<V,W>
+    where
+        V: Clone + Eq + Hash
+{
+    /// Does the map contain an item with `value`?
+    pub fn contains_value(&self, value: &V) -> bool
+    {
+        self.indices.contains_key(value)
+    }
+
+    /// Get the weight of the item with `value`, or `None` if there is no such item.
+    pub fn weight_of(&self, value: &V) -> Option<W>
+    {
+        self.indices.get(value).map(|&idx| (*self.list)[idx].weight)
+    }
+
+    /// Get the unweighted index of the item with `value`, or `None` if there is no such item.
+    pub fn position_of(&self, value: &V) -> Option<usize>
+    {
+        self.indices.get(value).copied()
+    }
+
+    /// Merge an item into the map. If an item with the same value already exists, add the weight of the new item to the existing item. Otherwise, append the new item to the map.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut wm = WeightedMap::new();
+    ///
+    /// wm.merge_item(wit!(2, "sup"))
+    ///   .merge_item(wit!(3, "nova"))
+    ///   .merge_item(wit!(5, "sup"));
+    ///
+    /// assert_eq!( wm.into_list(), wlist![(7, "sup"), (3, "nova")] );
+    /// ```
+    pub fn merge_item(&mut self, item: WeightedItem<V,W>) -> &mut Self
+    {
+        match self.indices.get(&item.value) {
+            Some(&idx) => {
+                (*self.list)[idx].weight += item.weight;
+            },
+            None => {
+                self.indices.insert(item.value.clone(), self.list.total_items());
+                self.list.push_item(item);
+            }
+        }
+
+        self
+    }
+
+    /// Merge a new item with `value` and `weight` into the map.
+    ///
+    /// See [`.merge_item()`](Self::merge_item) for details.
+    pub fn merge_new_item(&mut self, weight: W, value: V) -> &mut Self
+    {
+        self.merge_item(WeightedItem::new(weight, value))
+    }
+
+    /// Merge a new item with `value` and a weight of `1` into the map.
+    ///
+    /// See [`.merge_item()`](Self::merge_item) for details.
+    pub fn merge_value(&mut self, value: V) -> &mut Self
+    {
+        self.merge_item(WeightedItem::unit(value))
+    }
+
+    /// Set the weight of the item with `value` to `weight`, appending a new item if there is no such item. Returns the previous weight, if any.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut wm = WeightedMap::from_iter([(2, "sup")]);
+    ///
+    /// assert_eq!( wm.set_weight("sup", 5), Some(2) );
+    /// assert_eq!( wm.set_weight("nova", 3), None );
+    ///
+    /// assert_eq!( wm.into_list(), wlist![(5, "sup"), (3, "nova")] );
+    /// ```
+    pub fn set_weight(&mut self, value: V, weight: W) -> Option<W>
+    {
+        match self.indices.get(&value) {
+            Some(&idx) => {
+                Some(std::mem::replace(&mut (*self.list)[idx].weight, weight))
+            },
+            None => {
+                self.merge_new_item(weight, value);
+                None
+            }
+        }
+    }
+
+    /// Remove the item with `value` and return it, or `None` if there is no such item.
+    ///
+    /// Later items are shifted down to preserve their order, so this is $O(n)$ (see [§ Ordering](WeightedMap#ordering)).
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let mut wm = WeightedMap::from_iter([(2, "sup"), (3, "nova"), (5, "shard")]);
+    ///
+    /// assert_eq!( wm.remove_value(&"sup"), Some(wit!(2, "sup")) );
+    /// assert_eq!( wm.remove_value(&"sup"), None );
+    ///
+    /// assert_eq!( wm.into_list(), wlist![(3, "nova"), (5, "shard")] );
+    /// ```
+    pub fn remove_value(&mut self, value: &V) -> Option<WeightedItem<V,W>>
+    {
+        let idx = self.indices.remove(value)?;
+        Some(self._shift_remove_at_(idx))
+    }
+
+    /// Remove all items with non-positive weight.
+    pub fn prune(&mut self) -> &mut Self
+    {
+        self.list.prune();
+        self._reindex_();
+        self
+    }
+
+    /// Clear the map, removing all items.
+    pub fn clear(&mut self) -> &mut Self
+    {
+        self.list.clear();
+        self.indices.clear();
+        self
+    }
+}
+
+// == RANDOMISATION == //
+/// Methods for out-of-place random sampling from a map.
+///
+/// These behave identically to their [`WeightedList`] equivalents.
+impl<V, W: Weight> WeightedMap<V,W>
+{
+    /// Select a random item from the map and return its value, using weighted randomisation. See [`WeightedList::select_random_value()`].
+    pub fn select_random_value<RNG>(&self, rng: &mut RNG) -> Result<&V, Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        self.list.select_random_value(rng)
+    }
+
+    /// Select a random item from the map, using weighted randomisation. See [`WeightedList::select_random_item()`].
+    pub fn select_random_item<RNG>(&self, rng: &mut RNG) -> Result<&WeightedItem<V,W>, Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        self.list.select_random_item(rng)
+    }
+}
+
+/// Methods for in-place random sampling from a map, decreasing weights of items that are chosen.
+impl<V, W: Weight> WeightedMap<V,W>
+    where
+        V: Clone + Eq + Hash
+{
+    /// Select a random item from the map using weighted randomisation, and decrement its weight by `1`. See [`WeightedList::take_one_random()`].
+    pub fn take_one_random<RNG>(&mut self,
+        rng: &mut RNG
+    ) -> Result<WeightedItem<V,W>, Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        self.take_by_random(rng, W::one())
+    }
+
+    /// Select a random item from the map using weighted randomisation, and decrement its weight by `decrement`. If its weight becomes non-positive as a result, remove the entire item. See [`WeightedList::take_by_random()`].
+    pub fn take_by_random<RNG>(&mut self,
+        rng: &mut RNG,
+        decrement: W,
+    ) -> Result<WeightedItem<V,W>, Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        if self.list.is_empty() {
+            Err(EmptyWeightedList { reason: "Cannot take random values from an empty `WeightedMap`" })?
+        }
+
+        let weighted_index = self.list._get_random_weighted_index_(rng)?;
+        let idx = self.list._unweight_index_(weighted_index);
+        let target = &mut (*self.list)[idx];
+
+        if decrement >= target.weight {
+            target.weight = W::zero();
+            self.indices.remove(&target.value);
+            Ok(self._shift_remove_at_(idx))
+        }
+        else {
+            target.weight -= decrement;
+            Ok(target.clone())
+        }
+    }
+
+    /// Select and remove a random item from the map, using weighted randomisation. See [`WeightedList::take_entire_random()`].
+    pub fn take_entire_random<RNG>(&mut self, rng: &mut RNG) -> Result<WeightedItem<V,W>, Box<dyn Error>>
+        where RNG: Rng + ?Sized
+    {
+        if self.list.is_empty() {
+            Err(EmptyWeightedList { reason: "Cannot take random values from an empty `WeightedMap`" })?
+        }
+
+        let weighted_index = self.list._get_random_weighted_index_(rng)?;
+        let idx = self.list._unweight_index_(weighted_index);
+        self.indices.remove(&(*self.list)[idx].value);
+
+        Ok(self._shift_remove_at_(idx))
+    }
+}
+
+/// Random sampling methods which use the bon builder syntax.
+///
+/// These behave identically to their [`WeightedList`] equivalents.
+#[bon]
+impl<V, W: Weight> WeightedMap<V,W>
+    where
+        V: Clone + Eq + Hash
+{
+    /// Select `count` values using weighted randomisation. See [`WeightedList::select_random_values()`].
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use weighted_list::*;
+    /// let wm = WeightedMap::from_iter([(2, "sup"), (3, "nova"), (5, "shard")]);
+    ///
+    /// let selected = wm.select_random_values()
+    ///     .rng(&mut rand::rng())
+    ///     .count(3)
+    ///     .call();
+    ///
+    /// assert_eq!( selected.len(), 3 );
+    /// ```
+    #[builder]
+    pub fn select_random_values<RNG>(&self,
+        rng: &mut RNG,
+        count: usize,
+        replace: Option<bool>,
+        decrement: Option<W>,
+    ) -> Vec<V>
+        where RNG: Rng + ?Sized
+    {
+        self.list.select_random_values()
+            .rng(rng)
+            .count(count)
+            .maybe_replace(replace)
+            .maybe_decrement(decrement)
+            .call()
+    }
+
+    /// Select `count` unique values using weighted randomisation. See [`WeightedList::select_random_values_unique()`].
+    ///
+    /// Since values in a map are already unique, there is no `merge_duplicates` option.
+    #[builder]
+    pub fn select_random_values_unique<RNG>(&self,
+        rng: &mut RNG,
+        count: usize,
+    ) -> Vec<V>
+        where RNG: Rng + ?Sized
+    {
+        self.list.select_random_values_unique()
+            .rng(rng)
+            .count(count)
+            .call()
+    }
+
+    /// Take `count` values using weighted randomisation. See [`WeightedList::take_random_values()`].
+    #[builder]
+    pub fn take_random_values<RNG>(&mut self,
+        rng: &mut RNG,
+        count: usize,
+        take_entire: Option<bool>,
+        decrement: Option<W>,
+    ) -> Vec<V>
+        where RNG: Rng + ?Sized
+    {
+        let take_entire = take_entire.unwrap_or(true);
+        let decrement = decrement.unwrap_or(W::one());
+
+        let mut out = Vec::with_capacity(count);
+
+        for _ in 0..count
+        {
+            if self.list.is_empty() { break }
+
+            if let Ok(item) = {
+                if take_entire { self.take_entire_random(rng) }
+                else           { self.take_by_random(rng, decrement) }
+            } {
+                out.push(item.value);
+            }
+        }
+
+        out
+    }
+
+    /// Take `count` unique values using weighted randomisation. See [`WeightedList::take_random_values_unique()`].
+    #[builder]
+    pub fn take_random_values_unique<RNG>(&mut self,
+        rng: &mut RNG,
+        count: usize,
+        decrement: Option<W>,
+    ) -> Vec<V>
+        where RNG: Rng + ?Sized
+    {
+        let decrement = decrement.unwrap_or(W::one());
+
+        let out = self.select_random_values_unique()
+            .rng(rng)
+            .count(count)
+            .call();
+
+        for value in &out {
+            if let Some(&idx) = self.indices.get(value) {
+                let target = &mut (*self.list)[idx];
+
+                if decrement >= target.weight {
+                    target.weight = W::zero();
+                } else {
+                    target.weight -= decrement;
+                }
+            }
+        }
+
+        self.prune();
+
+        out
+    }
+}
+
+// == INTERNAL == //
+impl<V, W: Weight> WeightedMap<V,W>
+    where
+        V: Clone + Eq + Hash
+{
+    /// Remove the item at unweighted index `idx`, shifting later items down, and update the indices of the shifted items. Assumes the removed item's value has already been removed from `self.indices`.
+    fn _shift_remove_at_(&mut self, idx: usize) -> WeightedItem<V,W>
+    {
+        let out = AsMut::<Vec<_>>::as_mut(&mut self.list).remove(idx);
+
+        for (i, item) in self.list.iter().enumerate().skip(idx) {
+            if let Some(index) = self.indices.get_mut(&item.value) {
+                *index = i;
+            }
+        }
+
+        out
+    }
+
+    /// Rebuild `self.indices` from scratch after the list has been restructured.
+    fn _reindex_(&mut self)
+    {
+        self.indices.clear();
+
+        for (i, item) in self.list.iter().enumerate() {
+            self.indices.insert(item.value.clone(), i);
+        }
+    }
+}
+
+
+#[cfg(test)] mod tests
+{
+    use super::*;
+
+    fn wm() -> WeightedMap<String, i32>
+    {
+        WeightedMap::from_iter([
+            (2, "sup".to_string()),
+            (3, "nova".to_string()),
+            (5, "shard".to_string()),
+        ])
+    }
+
+    fn assert_indices_consistent(map: &WeightedMap<String, i32>)
+    {
+        assert_eq!( map.indices.len(), map.list.total_items() );
+
+        for (i, item) in map.list.iter().enumerate() {
+            assert_eq!( map.indices[&item.value], i );
+        }
+    }
+
+    #[test] fn _shift_remove_at_()
+    {
+        let mut map = wm();
+        map.indices.remove("sup");
+        map._shift_remove_at_(0);
+        assert_indices_consistent(&map);
+        assert_eq!( map.list.values().collect::<Vec<_>>(), vec!["nova", "shard"] );
+
+        let mut map = wm();
+        map.indices.remove("shard");
+        map._shift_remove_at_(2);
+        assert_indices_consistent(&map);
+    }
+
+    #[test] fn _reindex_()
+    {
+        let mut map = wm();
+        map.set_weight("sup".to_string(), 0);
+        map.prune();
+        assert_indices_consistent(&map);
+        assert_eq!( map.position_of(&"nova".to_string()), Some(0) );
+    }
+
+    #[test] fn take_keeps_indices_consistent()
+    {
+        let mut rng = rand::rng();
+        let mut map = wm();
+
+        while !map.is_empty() {
+            map.take_one_random(&mut rng).unwrap();
+            assert_indices_consistent(&map);
+        }
+    }
+}
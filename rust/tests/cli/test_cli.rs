@@ -0,0 +1,120 @@
+use std::io::{ Write };
+use std::process::{ Command, Stdio };
+
+
+const TABLE: &str = "2 sup\n3 nova\n5 shard\n";
+
+
+/// Run `wlist` with `args` and `stdin`, returning whether it succeeded and its stdout or stderr.
+fn wlist(args: &[&str], stdin: &str) -> (bool, String)
+{
+    let mut child = Command::new(env!("CARGO_BIN_EXE_wlist"))
+        .args(args)
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../interchange"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    let text = if output.status.success() { output.stdout } else { output.stderr };
+
+    (output.status.success(), String::from_utf8(text).unwrap())
+}
+
+
+#[test] fn sample()
+{
+    let (ok, out) = wlist(&["sample", "-n", "20", "--seed", "42"], TABLE);
+    assert!( ok );
+    assert_eq!( out.lines().count(), 20 );
+    assert!( out.lines().all(|line| ["sup", "nova", "shard"].contains(&line)) );
+
+    // seeded output is reproducible
+    assert_eq!( wlist(&["sample", "-n", "20", "--seed", "42"], TABLE).1, out );
+
+    let (_, out) = wlist(&["sample", "-n", "20", "--seed", "7", "--no-replace"], TABLE);
+    let mut lines = out.lines().collect::<Vec<_>>();
+    lines.sort();
+    assert_eq!( lines, [vec!["nova"; 3], vec!["shard"; 5], vec!["sup"; 2]].concat() );
+
+    let (_, out) = wlist(&["sample", "-n", "5", "--unique"], TABLE);
+    let mut lines = out.lines().collect::<Vec<_>>();
+    lines.sort();
+    assert_eq!( lines, ["nova", "shard", "sup"] );
+
+    let (ok, _) = wlist(&["sample", "--unique", "--no-replace"], TABLE);
+    assert!( !ok );
+}
+
+#[test] fn stats()
+{
+    let (ok, out) = wlist(&["stats"], TABLE);
+    assert!( ok );
+    assert_eq!(
+        out,
+        "items:   3\ntotal:   10\nentropy: 1.4855 bits\n\n\
+        weight  probability  value\n\
+        2       0.2000       sup\n\
+        3       0.3000       nova\n\
+        5       0.5000       shard\n"
+    );
+
+    let (_, out) = wlist(&["stats"], "");
+    assert_eq!( out, "items:   0\ntotal:   0\nentropy: 0.0000 bits\n" );
+}
+
+#[test] fn normalise()
+{
+    assert_eq!( wlist(&["normalise"], TABLE).1, "0.2  sup\n0.3  nova\n0.5  shard\n" );
+    assert_eq!( wlist(&["normalize", "--to", "csv"], TABLE).1, "weight,value\n0.2,sup\n0.3,nova\n0.5,shard\n" );
+}
+
+#[test] fn merge()
+{
+    let (ok, out) = wlist(&["merge", "valid/basic.json", "-"], "4 sup\n1 cortex");
+    assert!( ok );
+    assert_eq!( out, "6  sup\n3  nova\n5  shard\n1  cortex\n" );
+
+    let (_, out) = wlist(&["merge", "valid/float.json", "--to", "tsv"], "");
+    assert_eq!( out, "weight\tvalue\n2.5\tsup\n0.125\tnova\n7\tshard\n" );
+}
+
+#[test] fn diff()
+{
+    let (ok, out) = wlist(&["diff", "valid/basic.json", "-"], "4 sup\n3 nova\n1 cortex");
+    assert!( ok );
+    assert_eq!( out, "~ 2 -> 4  sup\n- 5  shard\n+ 1  cortex\n" );
+
+    assert_eq!( wlist(&["diff", "valid/basic.json", "valid/frozen.json"], "").1, "" );
+}
+
+#[test] fn formats()
+{
+    let (_, out) = wlist(&["normalise", "--format", "csv", "--value-column", "name"], "name,weight\nsup,1\nnova,3");
+    assert_eq!( out, "0.25  sup\n0.75  nova\n" );
+
+    let (_, out) = wlist(&["normalise", "-f", "tsv", "--weight-column", "0", "--value-column", "1"], "weight\tvalue\n1\tsup\n1\tnova");
+    assert_eq!( out, "0.5  sup\n0.5  nova\n" );
+
+    let (_, out) = wlist(&["stats", "valid/values.json"], "");
+    assert!( out.contains("{\"name\":\"sup\",\"rarity\":\"common\"}") );
+}
+
+#[test] fn errors()
+{
+    let (ok, err) = wlist(&["stats"], "x sup");
+    assert!( !ok );
+    assert_eq!( err, "wlist: Failed to read stdin: Invalid weighted list at line 1, column 1: Invalid weight\n" );
+
+    let (ok, err) = wlist(&["stats", "missing.txt"], "");
+    assert!( !ok );
+    assert!( err.starts_with("wlist: Failed to open missing.txt") );
+
+    let (ok, err) = wlist(&["stats", "invalid/future-version.json"], "");
+    assert!( !ok );
+    assert!( err.contains("Unsupported version 2") );
+}
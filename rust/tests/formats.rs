@@ -0,0 +1,38 @@
+// NOTE: Tests for features linking `serde_json` are kept apart from `mod.rs`, since its `PartialEq<Value>` impls break inference in other tests.
+mod utils;
+pub use utils::*;
+
+
+#[cfg(feature = "serde")]
+mod serde
+{
+    mod test_serde;
+}
+
+
+#[cfg(feature = "schemars")]
+mod schema
+{
+    mod test_schema;
+}
+
+
+#[cfg(feature = "json")]
+mod interchange
+{
+    mod test_interchange;
+}
+
+
+#[cfg(feature = "minecraft")]
+mod minecraft
+{
+    mod test_minecraft;
+}
+
+
+#[cfg(feature = "cli")]
+mod cli
+{
+    mod test_cli;
+}
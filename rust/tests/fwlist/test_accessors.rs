@@ -0,0 +1,47 @@
+use crate::*;
+use weighted_list::*;
+
+
+#[test] fn raw()
+{
+    let e: [(u32, &String); 0] = [];
+    assert!( efl().raw().eq(e) );
+
+    assert!(
+        fwl().raw().eq([
+            (2, &str!("sup")),
+            (3, &str!("nova")),
+            (5, &str!("shard")),
+        ])
+    );
+}
+
+#[test] fn collect_raw()
+{
+    assert_eq!( efl().collect_raw(), vec![] );
+
+    assert_eq!(
+        fwl().collect_raw(),
+        vec![
+            (2, &str!("sup")),
+            (3, &str!("nova")),
+            (5, &str!("shard")),
+        ]
+    );
+}
+
+#[test] fn into_raw()
+{
+    assert_eq!( efl().into_raw(), vec![] );
+    assert_eq!( fwl().into_raw(), data_string(false) );
+}
+
+#[test] fn unique_values()
+{
+    assert_eq!( efl().unique_values().count(), 0 );
+    assert!( fwl().unique_values().eq(["sup", "nova", "shard"]) );
+
+    let list = FWList::init([(2, "sup"), (3, "nova"), (5, "sup")]);
+    assert!( list.unique_values().eq(&["sup", "nova"]) );
+    assert!( list.unique_values_hashed().eq(&["sup", "nova"]) );
+}
@@ -0,0 +1,30 @@
+use crate::*;
+use weighted_list::*;
+
+
+#[test] fn display()
+{
+    assert_eq!( efl().to_string(), "" );
+    assert_eq!( fwl().to_string(), "2  sup\n3  nova\n5  shard\n" );
+}
+
+#[test] fn parse()
+{
+    let list: FWList<String, u32> = "2 sup\n3 | nova\n// comment\n5 \"shard\"".parse().unwrap();
+    assert_eq!( list, fwl() );
+    assert_eq!( list.items().map(|item| item.cumulative_weight()).collect::<Vec<_>>(), vec![2, 5, 10] );
+
+    assert_eq!( fwl().to_string().parse::<FWList<String, u32>>().unwrap(), fwl() );
+}
+
+#[test] fn parse_errors()
+{
+    let err = "2 sup\n-1 nova".parse::<FWList<String, i32>>().unwrap_err();
+    assert_eq!( err.to_string(), "Invalid weighted list at line 2, column 1: Weights must be non-negative" );
+
+    let err = "2 sup\n  NaN nova".parse::<FWList<String, f64>>().unwrap_err();
+    assert_eq!( err.to_string(), "Invalid weighted list at line 2, column 3: Weights must be non-negative" );
+
+    let err = "200 sup\n100 nova".parse::<FWList<String, u8>>().unwrap_err();
+    assert_eq!( err.to_string(), "Invalid weighted list at line 2, column 1: Total weight overflows the weight type" );
+}
@@ -0,0 +1,156 @@
+use weighted_list::*;
+
+
+const TRIALS: usize = 50;
+
+
+fn grammar() -> Grammar<u32>
+{
+    "
+    // flavour text for mobs
+    origin: #adjective.capitalise# #noun.s# lurk in the #place#.
+    adjective:
+        shiny
+        3 | dull
+    noun:
+        wolf
+        2 | box
+    place: cave
+    "
+    .parse().unwrap()
+}
+
+
+#[test] fn parse()
+{
+    let grammar = grammar();
+
+    let mut symbols = grammar.symbols().collect::<Vec<_>>();
+    symbols.sort();
+    assert_eq!( symbols, vec!["adjective", "noun", "origin", "place"] );
+
+    let adjectives = grammar.rule("adjective").unwrap();
+    assert_eq!( adjectives.weights().collect::<Vec<_>>(), vec![1, 3] );
+    assert_eq!( adjectives[0].value.as_str(), "shiny" );
+    assert_eq!( adjectives[1].value.as_str(), "dull" );
+
+    assert_eq!( grammar.rule("place").unwrap().total_items(), 1 );
+    assert!( grammar.rule("void").is_none() );
+}
+
+#[test] fn parse_merges_rules()
+{
+    let grammar: Grammar<u32> = "
+        a: x
+        b: y
+        a: 2 | z
+    ".parse().unwrap();
+
+    assert_eq!( grammar.rule("a").unwrap().total_items(), 2 );
+    assert_eq!( grammar.rule("a").unwrap().len(), 3 );
+
+    // a `|` not preceded by a weight is part of the template
+    let grammar: Grammar<u32> = "a: x | y".parse().unwrap();
+    assert_eq!( grammar.rule("a").unwrap()[0].value.as_str(), "x | y" );
+}
+
+#[test] fn parse_errors()
+{
+    for (source, line, column) in [
+        ("  indented without a rule", 1, 3),
+        ("a: x\nb c: y", 2, 1),
+        ("a:\n  #b", 2, 3),
+        ("a: 2 | #b", 1, 8),
+        ("a:\n  -1 | x", 2, 3),
+    ] {
+        let err = source.parse::<Grammar<i32>>().unwrap_err();
+        let message = err.to_string();
+
+        assert!(
+            message.contains(&format!("line {line}, column {column}")),
+            "{source:?} gave {message:?}"
+        );
+    }
+
+    assert!( "a: NaN | x".parse::<Grammar<f64>>().is_err() );
+    assert!( "a: -0.5 | x".parse::<Grammar<f64>>().is_err() );
+    assert!( "a: 0.5 | x".parse::<Grammar<f64>>().is_ok() );
+}
+
+#[test] fn expand()
+{
+    let mut rng = rand::rng();
+    let grammar = grammar();
+
+    for _ in 0..TRIALS {
+        let text = grammar.expand(&mut rng, "origin").unwrap();
+
+        assert!( text.starts_with("Shiny ") || text.starts_with("Dull ") );
+        assert!( text.contains(" wolfs ") || text.contains(" boxes ") );
+        assert!( text.ends_with(" lurk in the cave.") );
+    }
+
+    assert!( grammar.expand(&mut rng, "void").is_err() );
+}
+
+#[test] fn expand_bindings()
+{
+    let mut rng = rand::rng();
+
+    let grammar: Grammar<u32> = "
+        origin: [hero:#name#]#hero.capitalise# met #animal.a#. #hero.capitalise# liked the #animal#.
+        name:
+            ash
+            elm
+            oak
+        animal:
+            owl
+            yak
+    ".parse().unwrap();
+
+    for _ in 0..TRIALS {
+        let text = grammar.expand(&mut rng, "origin").unwrap();
+        let hero = &text[..3];
+
+        assert!( text.starts_with(&format!("{hero} met a")) );
+        assert!( text.contains(&format!(". {hero} liked the ")) );
+    }
+}
+
+#[test] fn expand_depth()
+{
+    let mut rng = rand::rng();
+
+    let mut grammar: Grammar<u32> = "
+        loop: a#loop#
+        deep: #deeper#
+        deeper: #deepest#
+        deepest: end
+    ".parse().unwrap();
+
+    assert_eq!( grammar.max_depth(), 32 );
+    assert!( grammar.expand(&mut rng, "loop").is_err() );
+    assert_eq!( grammar.expand(&mut rng, "deep").unwrap(), "end" );
+
+    grammar.set_max_depth(2);
+    assert!( grammar.expand(&mut rng, "deep").is_err() );
+    grammar.set_max_depth(3);
+    assert_eq!( grammar.expand(&mut rng, "deep").unwrap(), "end" );
+}
+
+#[test] fn expand_template()
+{
+    let mut rng = rand::rng();
+
+    let mut grammar = Grammar::<u32>::new();
+    grammar
+        .add_alternative("name", 1, Template::parse("ash").unwrap())
+        .set_rule("empty", WList::new());
+
+    let template = Template::parse("\\#[hero:#name#]#hero.s# and #name.capitalise#").unwrap();
+    assert_eq!( grammar.expand_template(&mut rng, &template).unwrap(), "#ashes and Ash" );
+
+    let template: Template = "#empty#".parse().unwrap();
+    assert!( grammar.expand_template(&mut rng, &template).is_err() );
+    assert_eq!( template.to_string(), "#empty#" );
+}
@@ -7,6 +7,10 @@
         Weight,
         WeightedItem, WItem,
         WeightedList, WList, wlist,
+        WeightedMap, WMap,
+        Mixture,
+        MarkovChain, MarkovWalk,
+        Grammar, Template,
     };
 }
 
@@ -17,3 +21,41 @@
         FrozenWeightedList, FWList, fwlist,
     };
 }
+
+#[test] #[cfg(feature = "csv")] fn csv_imports()
+{
+    use weighted_list::{
+        CsvColumn,
+    };
+}
+
+#[test] #[cfg(feature = "mmap")] fn mmap_imports()
+{
+    use weighted_list::{
+        MappedFrozenWeightedList, MFWList,
+        MappedWeight,
+    };
+}
+
+#[test] #[cfg(feature = "loot-config")] fn loot_config_imports()
+{
+    use weighted_list::{
+        LootConfig, LootTable, LootEntry,
+    };
+}
+
+#[test] #[cfg(feature = "minecraft")] fn minecraft_imports()
+{
+    use weighted_list::{
+        MinecraftLootTable, MinecraftPool, MinecraftEntry,
+        MinecraftNumber,
+        MinecraftWarning, MinecraftWarningKind,
+    };
+}
+
+#[test] #[cfg(feature = "registry")] fn registry_imports()
+{
+    use weighted_list::{
+        TableRegistry,
+    };
+}
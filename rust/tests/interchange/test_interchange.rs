@@ -0,0 +1,104 @@
+use crate::*;
+use weighted_list::*;
+
+use std::fs;
+use std::path::{ PathBuf };
+
+use serde_json::{ Value, json };
+
+
+fn fixture(name: &str) -> String
+{
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../interchange").join(name);
+    fs::read_to_string(&path).unwrap_or_else(|err| panic!("failed to read {path:?}: {err}"))
+}
+
+fn written<V, W>(list: &WList<V,W>) -> String
+    where
+        V: ::serde::Serialize,
+        W: Weight + ::serde::Serialize,
+{
+    let mut out = Vec::new();
+    list.write_interchange(&mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+
+#[test] fn golden()
+{
+    let list = WList::<String, u32>::read_interchange(fixture("valid/basic.json").as_bytes()).unwrap();
+    assert_eq!( list, wl() );
+    assert_eq!( written(&list), fixture("valid/basic.json") );
+
+    let list = WList::<String, f64>::read_interchange(fixture("valid/float.json").as_bytes()).unwrap();
+    assert_eq!( list, wlist![(2.5, str!("sup")), (0.125, str!("nova")), (7.0, str!("shard"))] );
+    assert_eq!( written(&list), fixture("valid/float.json") );
+
+    let list = WList::<String, u32>::read_interchange(fixture("valid/empty.json").as_bytes()).unwrap();
+    assert_eq!( list, el() );
+    assert_eq!( written(&list), fixture("valid/empty.json") );
+
+    let list = WList::<Value, u32>::read_interchange(fixture("valid/values.json").as_bytes()).unwrap();
+    assert_eq!( list.len(), 19 );
+    assert_eq!( list.collect_values()[1], &json!("unicode ✦ and \"escapes\"\n") );
+    assert_eq!( list.collect_values()[4], &json!({"name": "sup", "rarity": "common"}) );
+    assert_eq!( written(&list), fixture("valid/values.json") );
+}
+
+#[test] fn read()
+{
+    // frozen documents can be read into a `WeightedList`
+    let list = WList::<String, u32>::read_interchange(fixture("valid/frozen.json").as_bytes()).unwrap();
+    assert_eq!( list, wl() );
+
+    let list = WList::<String, f32>::read_interchange(fixture("valid/minimal.json").as_bytes()).unwrap();
+    assert_eq!( list, wlist![(1.0, str!("sup"))] );
+
+    // int weights can be read into float types, but not the other way around
+    let list = WList::<String, f64>::read_interchange(fixture("valid/basic.json").as_bytes()).unwrap();
+    assert_eq!( list, wlist![(2.0, str!("sup")), (3.0, str!("nova")), (5.0, str!("shard"))] );
+
+    let err = WList::<String, u32>::read_interchange(fixture("valid/float.json").as_bytes()).unwrap_err();
+    assert_eq!( err.to_string(), "Invalid interchange document: Cannot read float weights into integer type u32" );
+}
+
+#[test] fn read_invalid()
+{
+    for name in [
+        "wrong-format",
+        "future-version",
+        "unknown-weight-type",
+        "float-weight-in-int",
+        "negative-weight",
+        "missing-items",
+        "missing-weight",
+    ] {
+        let doc = fixture(&format!("invalid/{name}.json"));
+
+        assert!( WList::<String, i32>::read_interchange(doc.as_bytes()).is_err(), "{name}" );
+        assert!( WList::<String, f64>::read_interchange(doc.as_bytes()).is_err(), "{name}" );
+    }
+
+    let err = WList::<String, u32>::read_interchange(fixture("invalid/future-version.json").as_bytes()).unwrap_err();
+    assert_eq!( err.to_string(), "Invalid interchange document: Unsupported version 2, expected at most 1" );
+
+    let err = WList::<String, f64>::read_interchange(fixture("invalid/negative-weight.json").as_bytes()).unwrap_err();
+    assert_eq!( err.to_string(), "Invalid interchange document: Item 1 has weight -3.0, but weights must be non-negative" );
+}
+
+#[test] #[cfg(feature = "frozen")] fn frozen()
+{
+    let list = FWList::<String, u32>::read_interchange(fixture("valid/frozen.json").as_bytes()).unwrap();
+    assert_eq!( list, fwl() );
+    assert_eq!( list.items().map(|item| item.cumulative_weight()).collect::<Vec<_>>(), vec![2, 5, 10] );
+
+    let mut out = Vec::new();
+    list.write_interchange(&mut out).unwrap();
+    assert_eq!( String::from_utf8(out).unwrap(), fixture("valid/frozen.json") );
+
+    let list = FWList::<String, u32>::read_interchange(fixture("valid/basic.json").as_bytes()).unwrap();
+    assert_eq!( list, fwl() );
+
+    let doc = r#"{"format": "weighted-list", "version": 1, "weight_type": "int", "items": [{"weight": 200, "value": 1}, {"weight": 100, "value": 2}]}"#;
+    assert!( FWList::<u32, u8>::read_interchange(doc.as_bytes()).is_err() );
+}
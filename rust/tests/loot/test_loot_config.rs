@@ -0,0 +1,227 @@
+use crate::*;
+use weighted_list::*;
+
+
+const CONFIG: &str = r#"
+[tables.chest]
+rolls = "2-4"
+guaranteed = [{ item = "torch" }]
+entries = [
+    { weight = 5, item = "stick" },
+    { weight = 3, item = "iron" },
+    { weight = 2, table = "treasure" },
+]
+
+[tables.treasure]
+rolls = 1
+entries = [
+    { weight = 1, item = "diamond" },
+    { item = "emerald" },
+]
+"#;
+
+fn errors<W: Weight>(result: Result<LootConfig<W>, Vec<errors::LootConfigError>>) -> Vec<String>
+{
+    result.unwrap_err().iter().map(ToString::to_string).collect()
+}
+
+
+#[test] fn parse_toml()
+{
+    let config = LootConfig::<u32>::from_toml(CONFIG).unwrap();
+
+    assert_eq!( config.table_names().collect::<Vec<_>>(), vec!["chest", "treasure"] );
+    assert!( config.table("barrel").is_none() );
+
+    let chest = config.table("chest").unwrap();
+    assert_eq!( chest.rolls(), &(2..=4) );
+    assert_eq!( chest.guaranteed(), &[LootEntry::Item(str!("torch"))] );
+    assert_eq!( chest.entries(), &wlist![
+        (5, LootEntry::Item(str!("stick"))),
+        (3, LootEntry::Item(str!("iron"))),
+        (2, LootEntry::Table(str!("treasure"))),
+    ]);
+
+    // weights and rolls default to 1
+    let treasure = config.table("treasure").unwrap();
+    assert_eq!( treasure.rolls(), &(1..=1) );
+    assert_eq!( treasure.entries().weights().collect::<Vec<_>>(), vec![1, 1] );
+}
+
+#[test] fn parse_ron()
+{
+    let config = LootConfig::<f64>::from_ron(r#"(tables: {
+        "chest": (rolls: "2-4", guaranteed: [(item: "torch")], entries: [(weight: 2.5, item: "stick"), (table: "treasure")]),
+        "treasure": (entries: [(item: "diamond")]),
+    })"#).unwrap();
+
+    let chest = config.table("chest").unwrap();
+    assert_eq!( chest.rolls(), &(2..=4) );
+    assert_eq!( chest.entries().weights().collect::<Vec<_>>(), vec![2.5, 1.0] );
+}
+
+#[test] fn roll()
+{
+    let config = LootConfig::<u32>::from_toml(CONFIG).unwrap();
+    let mut rng = rand::rng();
+
+    for _ in 0..100 {
+        let loot = config.roll("chest", &mut rng).unwrap();
+
+        assert_eq!( loot[0], "torch" );
+        assert!( (3..=5).contains(&loot.len()) );
+        assert!( loot[1..].iter().all(|item| ["stick", "iron", "diamond", "emerald"].contains(item)) );
+    }
+
+    assert_eq!(
+        config.roll("barrel", &mut rng).unwrap_err().to_string(),
+        "Invalid loot config at tables.barrel: Unknown table"
+    );
+}
+
+#[test] fn roll_guaranteed_only()
+{
+    let config = LootConfig::<u32>::from_toml(r#"
+        [tables.starter]
+        guaranteed = [{ item = "sword" }, { item = "shield" }]
+    "#).unwrap();
+
+    assert_eq!( config.roll("starter", &mut rand::rng()).unwrap(), vec!["sword", "shield"] );
+}
+
+#[test] fn unknown_references()
+{
+    assert_eq!(
+        errors(LootConfig::<u32>::from_toml(r#"
+            [tables.chest]
+            guaranteed = [{ table = "tools" }]
+            entries = [{ item = "stick" }, { table = "treasure" }]
+        "#)),
+        vec![
+            "Invalid loot config at tables.chest.guaranteed[0].table: Unknown table \"tools\"",
+            "Invalid loot config at tables.chest.entries[1].table: Unknown table \"treasure\"",
+        ]
+    );
+}
+
+#[test] fn cycles()
+{
+    assert_eq!(
+        errors(LootConfig::<u32>::from_toml(r#"
+            [tables.a]
+            entries = [{ table = "b" }]
+
+            [tables.b]
+            entries = [{ item = "stick" }, { table = "c" }]
+
+            [tables.c]
+            guaranteed = [{ table = "a" }]
+
+            [tables.d]
+            entries = [{ table = "d" }]
+        "#)),
+        vec![
+            "Invalid loot config at tables.a: Cycle of table references a -> b -> c -> a",
+            "Invalid loot config at tables.d: Cycle of table references d -> d",
+        ]
+    );
+}
+
+#[test] fn invalid_weights()
+{
+    assert_eq!(
+        errors(LootConfig::<u32>::from_toml(r#"
+            [tables.chest]
+            guaranteed = [{ weight = 2, item = "torch" }]
+            entries = [{ weight = -1, item = "stick" }, { weight = 1.5, item = "iron" }, { weight = nan, item = "gold" }]
+        "#)),
+        vec![
+            "Invalid loot config at tables.chest.guaranteed[0].weight: Guaranteed entries cannot have a weight",
+            "Invalid loot config at tables.chest.entries[0].weight: Weight must be non-negative, found -1",
+            "Invalid loot config at tables.chest.entries[1].weight: Weight must be an integer, found 1.5",
+            "Invalid loot config at tables.chest.entries[2].weight: Weight must be finite, found NaN",
+        ]
+    );
+
+    // fractional weights are fine for float lists
+    assert!( LootConfig::<f32>::from_toml("[tables.chest]\nentries = [{ weight = 1.5, item = \"iron\" }]").is_ok() );
+}
+
+#[test] fn invalid_entries()
+{
+    assert_eq!(
+        errors(LootConfig::<u32>::from_toml(r#"
+            [tables.chest]
+            entries = [{ item = "stick", table = "chest" }, { weight = 1 }]
+        "#)),
+        vec![
+            "Invalid loot config at tables.chest.entries[0]: Expected exactly one of `item` or `table`",
+            "Invalid loot config at tables.chest.entries[1]: Expected exactly one of `item` or `table`",
+        ]
+    );
+}
+
+#[test] fn invalid_rolls()
+{
+    assert_eq!(
+        errors(LootConfig::<u32>::from_toml(r#"
+            [tables.a]
+            rolls = "4-2"
+            entries = [{ item = "stick" }]
+
+            [tables.b]
+            rolls = "some"
+            entries = [{ item = "stick" }]
+        "#)),
+        vec![
+            "Invalid loot config at tables.a.rolls: Minimum rolls exceed maximum rolls",
+            "Invalid loot config at tables.b.rolls: Expected a count like `3` or a range like `2-4`",
+        ]
+    );
+}
+
+#[test] fn empty_tables()
+{
+    assert_eq!(
+        errors(LootConfig::<u32>::from_toml(r#"
+            [tables.a]
+
+            [tables.b]
+            entries = [{ weight = 0, item = "stick" }]
+
+            [tables.c]
+            rolls = 1
+            guaranteed = [{ item = "stick" }]
+
+            [tables.d]
+            guaranteed = [{ item = "stick" }]
+        "#)),
+        vec![
+            "Invalid loot config at tables.a: Table is empty",
+            "Invalid loot config at tables.b: Table has no entries with positive weight to roll",
+            "Invalid loot config at tables.c: Table has no entries with positive weight to roll",
+        ]
+    );
+}
+
+#[test] fn syntax_errors()
+{
+    let toml = errors(LootConfig::<u32>::from_toml("[tables.chest]\nentries = [{ item = \"stick\" }\n"));
+    assert_eq!( toml.len(), 1 );
+    assert!( toml[0].starts_with("Invalid loot config at line 2, column "), "{}", toml[0] );
+
+    let toml = errors(LootConfig::<u32>::from_toml("[tables.chest]\nentries = [{ item = \"stick\", rarity = 2 }]\n"));
+    assert!( toml[0].starts_with("Invalid loot config at line 2, column 30: unknown field `rarity`"), "{}", toml[0] );
+
+    let ron = errors(LootConfig::<u32>::from_ron("(tables: {\n  \"chest\": (entries: [(item: 3)]),\n})"));
+    assert!( ron[0].starts_with("Invalid loot config at line 2, column "), "{}", ron[0] );
+}
+
+#[test] #[cfg(feature = "frozen")] fn frozen_entries()
+{
+    let config = LootConfig::<u32>::from_toml(CONFIG).unwrap();
+    let frozen = config.table("treasure").unwrap().frozen_entries();
+
+    assert_eq!( frozen.len(), 2 );
+    assert_eq!( frozen[1].value(), &LootEntry::Item(str!("emerald")) );
+}
@@ -0,0 +1,148 @@
+use crate::*;
+use weighted_list::*;
+
+use std::fs;
+use std::path::{ PathBuf };
+
+
+/// Get a path in the temporary directory which is unique to this test.
+fn temp_path(name: &str) -> PathBuf
+{
+    std::env::temp_dir().join(format!("weighted-list-test-{}-{name}.bin", std::process::id()))
+}
+
+fn write_and_open<W: MappedWeight>(name: &str, list: &WList<String, W>) -> MFWList<W>
+{
+    let path = temp_path(name);
+    list.write_binary(fs::File::create(&path).unwrap()).unwrap();
+
+    unsafe { MFWList::open(&path).unwrap() }
+}
+
+
+#[test] fn round_trip()
+{
+    let mapped = write_and_open("round-trip", &wl());
+
+    assert_eq!( mapped.total_items(), 3 );
+    assert_eq!( mapped.len(), 10 );
+    assert!( !mapped.is_empty() );
+    assert!( !mapped.is_zero() );
+
+    assert_eq!( mapped.weights().collect::<Vec<_>>(), vec![2, 3, 5] );
+    assert_eq!( mapped.values().collect::<Vec<_>>(), vec![b"sup".as_slice(), b"nova", b"shard"] );
+    assert_eq!( mapped.raw().map(|(weight, value)| (weight, String::from_utf8(value.to_vec()).unwrap())).collect::<WList<_,_>>(), wl() );
+
+    assert_eq!( mapped.cumulative_weight_at(1), Some(5) );
+    assert_eq!( mapped.weight_at(1), Some(3) );
+    assert_eq!( mapped.value_at(2), Some(b"shard".as_slice()) );
+    assert_eq!( mapped.value_at(3), None );
+}
+
+#[test] fn empty()
+{
+    let mapped = write_and_open("empty", &el());
+
+    assert_eq!( mapped.total_items(), 0 );
+    assert_eq!( mapped.len(), 0 );
+    assert!( mapped.is_empty() );
+    assert_eq!( mapped.get_value(0), None );
+    assert!( mapped.select_random_value(&mut rand::rng()).is_err() );
+}
+
+#[test] fn indexing()
+{
+    let mapped = write_and_open("indexing", &wl());
+
+    let values = (0..10).map(|idx| mapped.get_value(idx).unwrap()).collect::<Vec<_>>();
+    assert_eq!(
+        values,
+        [vec![b"sup".as_slice(); 2], vec![b"nova".as_slice(); 3], vec![b"shard".as_slice(); 5]].concat()
+    );
+
+    assert_eq!( mapped.position_at(4), Some(1) );
+    assert_eq!( mapped.position_at(10), None );
+    assert_eq!( mapped.get_value(10), None );
+
+    let list = wlist![(0.5, str!("sup")), (0.0, str!("skipped")), (1.5, str!("nova"))];
+    let mapped = write_and_open("indexing-float", &list);
+
+    assert_eq!( mapped.position_at(0.0), Some(0) );
+    assert_eq!( mapped.position_at(0.5), Some(2) );
+    assert_eq!( mapped.position_at(1.99), Some(2) );
+    assert_eq!( mapped.position_at(2.0), None );
+    assert_eq!( mapped.position_at(-0.5), None );
+    assert_eq!( mapped.position_at(f64::NAN), None );
+}
+
+#[test] fn random()
+{
+    let list = wlist![(0, str!("never")), (1, str!("sup")), (0, str!("never")), (3, str!("nova"))];
+    let mapped = write_and_open("random", &list);
+    let mut rng = rand::rng();
+
+    let mut counts = [0; 4];
+    for _ in 0..4000 {
+        counts[mapped.select_random_index(&mut rng).unwrap()] += 1;
+    }
+
+    assert_eq!( counts[0] + counts[2], 0 );
+    assert!( (800..1200).contains(&counts[1]) );
+
+    let list = wlist![(0.25, str!("sup")), (0.75, str!("nova"))];
+    let mapped = write_and_open("random-float", &list);
+
+    let novas = (0..4000)
+        .filter(|_| mapped.select_random_value(&mut rng).unwrap() == b"nova")
+        .count();
+    assert!( (2800..3200).contains(&novas) );
+}
+
+#[test] fn write_errors()
+{
+    let list = wlist![(2, str!("sup")), (-1, str!("nova"))];
+    let err = list.write_binary(Vec::new()).unwrap_err();
+    assert_eq!( err.to_string(), "Invalid binary weighted list: Item 1 has weight -1, but weights must be non-negative" );
+
+    let list = wlist![(200_u8, str!("sup")), (100, str!("nova"))];
+    let mut out = Vec::new();
+    assert!( list.write_binary(&mut out).is_err() );
+    assert!( out.is_empty() );
+}
+
+#[test] fn open_errors()
+{
+    let path = temp_path("open-errors");
+
+    let mut bytes = Vec::new();
+    wl().write_binary(&mut bytes).unwrap();
+
+    let open = |bytes: &[u8]| {
+        fs::write(&path, bytes).unwrap();
+        unsafe { MFWList::<u32>::open(&path) }.err().map(|err| err.to_string())
+    };
+
+    assert_eq!( open(&bytes), None );
+    assert_eq!( open(b"not a list").unwrap(), "Invalid binary weighted list: Missing `WLISTBIN` header" );
+    assert!( open(&bytes[..bytes.len() - 1]).unwrap().contains("File has length") );
+
+    let mut wrong_version = bytes.clone();
+    wrong_version[8] = 2;
+    assert_eq!( open(&wrong_version).unwrap(), "Invalid binary weighted list: Unsupported version 2, expected 1" );
+
+    fs::write(&path, &bytes).unwrap();
+    let err = unsafe { MFWList::<f64>::open(&path) }.err().unwrap();
+    assert!( err.to_string().contains("does not match f64") );
+
+    assert!( unsafe { MFWList::<u32>::open(temp_path("missing")) }.is_err() );
+}
+
+#[test] #[cfg(feature = "frozen")] fn frozen()
+{
+    let path = temp_path("frozen");
+    fwl().write_binary(fs::File::create(&path).unwrap()).unwrap();
+
+    let mapped = unsafe { MFWList::<u32>::open(&path).unwrap() };
+    assert_eq!( mapped.len(), fwl().len() );
+    assert_eq!( mapped.get_value(9), Some(b"shard".as_slice()) );
+}
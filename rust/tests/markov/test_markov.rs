@@ -0,0 +1,161 @@
+use weighted_list::*;
+
+
+const TRIALS: usize = 50;
+
+
+fn approx(left: f64, right: f64) -> bool
+{
+    (left - right).abs() < 1e-9
+}
+
+
+#[test] fn train()
+{
+    let mut chain = MarkovChain::<char, u32>::new(1);
+    chain.train("abracadabra".chars());
+
+    assert_eq!( chain.order(), 1 );
+    assert_eq!( chain.transitions(&['a']), Some(&wlist![(2, 'b'), (1, 'c'), (1, 'd')]) );
+    assert_eq!( chain.transitions(&['r']), Some(&wlist![(2, 'a')]) );
+    assert_eq!( chain.transitions(&['z']), None );
+    assert_eq!(
+        chain.contexts().map(|(context, _)| context[0]).collect::<String>(),
+        "abrcd"
+    );
+
+    // training again accumulates counts
+    chain.train("ab".chars());
+    assert_eq!( chain.transitions(&['a']), Some(&wlist![(3, 'b'), (1, 'c'), (1, 'd')]) );
+}
+
+#[test] fn train_order()
+{
+    let mut chain = MarkovChain::<&str, u32>::new(2);
+    chain.train("the cat sat on the cat mat".split(' '));
+
+    assert_eq!( chain.transitions(&["the", "cat"]), Some(&wlist![(1, "sat"), (1, "mat")]) );
+    assert_eq!( chain.transitions(&["the"]), None );
+    assert!( chain.is_terminal(&["cat", "mat"]) );
+    assert!( !chain.is_terminal(&["on", "the"]) );
+
+    let mut chain = MarkovChain::<&str, u32>::new(0);
+    chain.train(["sup", "nova", "sup"]);
+    assert_eq!( chain.transitions(&[]), Some(&wlist![(2, "sup"), (1, "nova")]) );
+
+    let mut chain = MarkovChain::<&str, u32>::new(3);
+    chain.train(["sup", "nova"]);
+    assert_eq!( chain.contexts().count(), 0 );
+}
+
+#[test] #[should_panic] fn add_transition_wrong_order()
+{
+    MarkovChain::<&str, u32>::new(2).add_transition(vec!["sup"], 1, "nova");
+}
+
+#[test] fn next_state()
+{
+    let mut rng = rand::rng();
+
+    let mut chain = MarkovChain::<&str, u32>::new(2);
+    chain.train(["sup", "nova", "shard", "cortex"]);
+
+    assert_eq!( chain.next_state(&mut rng, &["sup", "nova"]), Some(&"shard") );
+    assert_eq!( chain.next_state(&mut rng, &["sup", "sup", "nova"]), Some(&"shard") );
+    assert_eq!( chain.next_state(&mut rng, &["nova"]), None );
+    assert_eq!( chain.next_state(&mut rng, &["shard", "cortex"]), None );
+}
+
+#[test] fn walk()
+{
+    let mut rng = rand::rng();
+
+    let mut chain = MarkovChain::<&str, u32>::new(1);
+    chain.train(["sup", "nova", "shard", "cortex"]);
+
+    assert_eq!(
+        chain.walk(&mut rng, ["sup"]).collect::<Vec<_>>(),
+        vec!["nova", "shard", "cortex"]
+    );
+    assert_eq!(
+        chain.walk(&mut rng, ["origin", "nova"]).collect::<Vec<_>>(),
+        vec!["shard", "cortex"]
+    );
+    assert_eq!( chain.walk(&mut rng, ["cortex"]).count(), 0 );
+    assert_eq!( chain.walk(&mut rng, []).count(), 0 );
+
+    let mut chain = MarkovChain::<char, u32>::new(1);
+    chain.train("abab".chars());
+    for _ in 0..TRIALS {
+        assert_eq!( chain.walk(&mut rng, ['a']).take(5).collect::<String>(), "babab" );
+    }
+}
+
+#[test] fn stationary_distribution()
+{
+    let mut chain = MarkovChain::<&str, u32>::new(1);
+    chain
+        .add_transition(vec!["sunny"], 9, "sunny")
+        .add_transition(vec!["sunny"], 1, "rainy")
+        .add_transition(vec!["rainy"], 1, "sunny")
+        .add_transition(vec!["rainy"], 1, "rainy");
+
+    let stationary = chain.stationary_distribution().unwrap();
+    let weights = stationary.weights().collect::<Vec<_>>();
+
+    assert_eq!( stationary.values().copied().collect::<Vec<_>>(), vec!["sunny", "rainy"] );
+    assert!( approx(weights[0], 5.0 / 6.0) );
+    assert!( approx(weights[1], 1.0 / 6.0) );
+}
+
+#[test] fn stationary_distribution_periodic()
+{
+    let mut chain = MarkovChain::<char, u32>::new(1);
+    chain.train("abca".chars());
+
+    let stationary = chain.stationary_distribution().unwrap();
+    for weight in stationary.weights() {
+        assert!( approx(weight, 1.0 / 3.0) );
+    }
+}
+
+#[test] fn stationary_distribution_slow()
+{
+    // moving between states is so rare that iterating cannot settle
+    let mut chain = MarkovChain::<char, u32>::new(1);
+    chain
+        .add_transition(vec!['a'], 1_000_000_000, 'a')
+        .add_transition(vec!['a'], 1, 'b')
+        .add_transition(vec!['b'], 1_000_000_000, 'b')
+        .add_transition(vec!['b'], 2, 'a');
+
+    assert!( chain.stationary_distribution().is_err() );
+}
+
+#[test] fn stationary_distribution_terminal()
+{
+    let mut chain = MarkovChain::<char, u32>::new(1);
+    chain.train("abc".chars());
+
+    let stationary = chain.stationary_distribution().unwrap();
+    assert_eq!( stationary.values().copied().collect::<String>(), "abc" );
+    assert!( approx(stationary.iter().last().unwrap().weight, 1.0) );
+}
+
+#[test] fn stationary_distribution_order()
+{
+    let mut chain = MarkovChain::<&str, u32>::new(0);
+    chain.train(["sup", "nova", "sup", "sup"]);
+    assert_eq!( chain.stationary_distribution().unwrap(), wlist![(0.75, "sup"), (0.25, "nova")] );
+
+    let mut chain = MarkovChain::<char, u32>::new(2);
+    chain.train("aabaabaab".chars());
+
+    let stationary = chain.stationary_distribution().unwrap();
+    let weights = stationary.weights().collect::<Vec<_>>();
+    assert_eq!( stationary.values().copied().collect::<String>(), "ab" );
+    assert!( approx(weights[0], 2.0 / 3.0) );
+    assert!( approx(weights[1], 1.0 / 3.0) );
+
+    assert!( MarkovChain::<char, u32>::new(1).stationary_distribution().unwrap().is_empty() );
+}
@@ -0,0 +1,173 @@
+use crate::*;
+use weighted_list::*;
+
+
+const TABLE: &str = r#"{
+    "type": "minecraft:chest",
+    "pools": [
+        {
+            "rolls": { "type": "minecraft:uniform", "min": 2, "max": 4 },
+            "bonus_rolls": 1.0,
+            "entries": [
+                { "type": "minecraft:item", "name": "minecraft:stick", "weight": 10, "quality": -3,
+                  "functions": [{ "function": "minecraft:set_count", "count": 3 }] },
+                { "type": "minecraft:item", "name": "minecraft:diamond", "quality": 2 },
+                { "type": "minecraft:tag", "name": "minecraft:logs", "expand": true, "weight": 2 },
+                { "type": "minecraft:empty", "weight": 5 }
+            ]
+        },
+        {
+            "rolls": 1,
+            "conditions": [{ "condition": "minecraft:random_chance", "chance": 0.5 }],
+            "entries": [
+                { "type": "minecraft:loot_table", "value": "minecraft:chests/simple_dungeon" },
+                { "type": "minecraft:alternatives", "children": [] }
+            ]
+        }
+    ],
+    "functions": [{ "function": "minecraft:explosion_decay" }]
+}"#;
+
+fn table() -> MinecraftLootTable
+{
+    MinecraftLootTable::read_json(TABLE.as_bytes()).unwrap()
+}
+
+
+#[test] fn import()
+{
+    let table = table();
+    let pools = table.pools();
+
+    assert_eq!( pools.len(), 2 );
+    assert_eq!( pools[0].rolls(), MinecraftNumber::Uniform { min: 2.0, max: 4.0 } );
+    assert_eq!( pools[0].bonus_rolls(), MinecraftNumber::Constant(1.0) );
+    assert_eq!( pools[1].rolls(), MinecraftNumber::Constant(1.0) );
+    assert_eq!( pools[1].bonus_rolls(), MinecraftNumber::Constant(0.0) );
+
+    assert_eq!(
+        pools[0].entries().collect::<Vec<_>>(),
+        vec![
+            (&MinecraftEntry::Item(str!("minecraft:stick")), 10, -3),
+            (&MinecraftEntry::Item(str!("minecraft:diamond")), 1, 2),
+            (&MinecraftEntry::Tag(str!("minecraft:logs")), 2, 0),
+            (&MinecraftEntry::Empty, 5, 0),
+        ]
+    );
+
+    // composite entries are left out
+    assert_eq!(
+        pools[1].entries().collect::<Vec<_>>(),
+        vec![(&MinecraftEntry::Table(str!("minecraft:chests/simple_dungeon")), 1, 0)]
+    );
+}
+
+#[test] fn luck()
+{
+    let table = table();
+    let pool = &table.pools()[0];
+
+    assert_eq!( pool.weighted_list(0.0).weights().collect::<Vec<_>>(), vec![10, 1, 2, 5] );
+    assert_eq!( pool.weighted_list(1.0).weights().collect::<Vec<_>>(), vec![7, 3, 2, 5] );
+    assert_eq!( pool.weighted_list(2.5).weights().collect::<Vec<_>>(), vec![2, 6, 2, 5] );
+
+    // weights never go negative
+    assert_eq!( pool.weighted_list(5.0).weights().collect::<Vec<_>>(), vec![0, 11, 2, 5] );
+    assert_eq!( pool.weighted_list(-1.0).weights().collect::<Vec<_>>(), vec![13, 0, 2, 5] );
+
+    assert_eq!( pool.weighted_list(0.0).values().nth(2), Some(&MinecraftEntry::Tag(str!("minecraft:logs"))) );
+}
+
+#[test] fn warnings()
+{
+    let table = table();
+
+    assert_eq!(
+        table.warnings().iter().map(|warning| (warning.kind(), warning.name(), warning.location())).collect::<Vec<_>>(),
+        vec![
+            (MinecraftWarningKind::Function, "minecraft:explosion_decay", "functions[0]"),
+            (MinecraftWarningKind::Function, "minecraft:set_count", "pools[0].entries[0].functions[0]"),
+            (MinecraftWarningKind::Condition, "minecraft:random_chance", "pools[1].conditions[0]"),
+            (MinecraftWarningKind::Entry, "minecraft:alternatives", "pools[1].entries[1]"),
+        ]
+    );
+
+    assert_eq!(
+        table.warnings()[1].to_string(),
+        "Unsupported function \"minecraft:set_count\" at pools[0].entries[0].functions[0]"
+    );
+}
+
+#[test] fn number_providers()
+{
+    let table = MinecraftLootTable::read_json(r#"{ "pools": [
+        { "rolls": { "min": 1, "max": 2 }, "entries": [] },
+        { "rolls": { "type": "minecraft:constant", "value": 3 }, "entries": [] },
+        { "rolls": { "type": "minecraft:binomial", "n": 5, "p": 0.5 }, "entries": [] },
+        { "rolls": { "type": "minecraft:score", "target": "this", "score": "luck" }, "entries": [] }
+    ]}"#.as_bytes()).unwrap();
+
+    let rolls = table.pools().iter().map(MinecraftPool::rolls).collect::<Vec<_>>();
+
+    assert_eq!( rolls, vec![
+        MinecraftNumber::Uniform { min: 1.0, max: 2.0 },
+        MinecraftNumber::Constant(3.0),
+        MinecraftNumber::Binomial { n: 5, p: 0.5 },
+        MinecraftNumber::Constant(1.0),
+    ]);
+
+    assert_eq!( table.warnings().len(), 1 );
+    assert_eq!( table.warnings()[0].kind(), MinecraftWarningKind::Number );
+    assert_eq!( table.warnings()[0].location(), "pools[3].rolls" );
+}
+
+#[test] fn roll()
+{
+    let table = table();
+    let mut rng = rand::rng();
+
+    for _ in 0..100 {
+        let count = table.pools()[0].roll_count(0.0, &mut rng);
+        assert!( (2..=4).contains(&count) );
+
+        let count = table.pools()[0].roll_count(2.0, &mut rng);
+        assert!( (4..=6).contains(&count) );
+
+        let loot = table.roll(0.0, &mut rng).unwrap();
+
+        assert!( (1..=5).contains(&loot.len()) );
+        assert!( !loot.contains(&&MinecraftEntry::Empty) );
+        assert_eq!( loot.last(), Some(&&MinecraftEntry::Table(str!("minecraft:chests/simple_dungeon"))) );
+    }
+}
+
+#[test] fn roll_zero_weights()
+{
+    let table = MinecraftLootTable::read_json(r#"{ "pools": [{
+        "rolls": 3,
+        "entries": [{ "type": "minecraft:item", "name": "minecraft:stick", "weight": 1, "quality": -1 }]
+    }]}"#.as_bytes()).unwrap();
+
+    assert_eq!( table.roll(0.0, &mut rand::rng()).unwrap().len(), 3 );
+    assert!( table.roll(1.0, &mut rand::rng()).unwrap().is_empty() );
+}
+
+#[test] fn invalid()
+{
+    let error = |json: &str| MinecraftLootTable::read_json(json.as_bytes()).unwrap_err().to_string();
+
+    assert_eq!( error("[]"), "Invalid Minecraft loot table at table: Expected an object" );
+    assert_eq!( error(r#"{ "pools": {} }"#), "Invalid Minecraft loot table at pools: Expected an array" );
+    assert_eq!( error(r#"{ "pools": [{ "entries": [] }] }"#), "Invalid Minecraft loot table at pools[0]: Missing `rolls`" );
+
+    assert_eq!(
+        error(r#"{ "pools": [{ "rolls": 1, "entries": [{ "type": "minecraft:item" }] }] }"#),
+        "Invalid Minecraft loot table at pools[0].entries[0]: Missing `name`"
+    );
+    assert_eq!(
+        error(r#"{ "pools": [{ "rolls": 1, "entries": [{ "type": "minecraft:item", "name": "a", "weight": -2 }] }] }"#),
+        "Invalid Minecraft loot table at pools[0].entries[0]: Invalid weight -2, expected a non-negative integer"
+    );
+
+    assert!( MinecraftLootTable::read_json("{".as_bytes()).is_err() );
+}
@@ -0,0 +1,81 @@
+use crate::*;
+use weighted_list::*;
+
+
+const TRIALS: usize = 200;
+
+
+/// Always generates the greatest possible value, to exercise rounding at the upper end.
+struct MaxRng;
+
+impl rand::RngCore for MaxRng
+{
+    fn next_u32(&mut self) -> u32 { u32::MAX }
+    fn next_u64(&mut self) -> u64 { u64::MAX }
+    fn fill_bytes(&mut self, dst: &mut [u8]) { dst.fill(u8::MAX) }
+}
+
+
+fn mixture() -> Mixture<String, u32>
+{
+    let mut out = Mixture::new();
+    out.push_component(3, wl())
+        .push_component(0, wll())
+        .push_component(5, WList::new())
+        .push_component(1, wlist![(4, str!("cortex"))]);
+    out
+}
+
+
+#[test] fn constructors()
+{
+    let from_list = Mixture::from(wlist![(3, wl()), (1, wll())]);
+    let from_iter = Mixture::from_iter([(3, wl()), (1, wll())]);
+
+    assert_eq!( from_list, from_iter );
+    assert_eq!( from_list.components().total_items(), 2 );
+    assert_eq!( from_list.into_components(), wlist![(3, wl()), (1, wll())] );
+
+    assert_eq!( Mixture::<String, u32>::default(), Mixture::new() );
+}
+
+#[test] fn flatten()
+{
+    assert_eq!(
+        Mixture::from(wlist![(4, wlist![(1, "sup"), (3, "nova")])]).flatten().unwrap(),
+        wlist![(1.0, "sup"), (3.0, "nova")]
+    );
+}
+
+#[test] fn select()
+{
+    let mut rng = rand::rng();
+    let mixture = mixture();
+
+    for _ in 0..TRIALS {
+        let value = mixture.select_random_value(&mut rng).unwrap();
+        assert!( ["sup", "nova", "shard", "cortex"].contains(&value.as_str()) );
+    }
+
+    assert!( Mixture::<String, u32>::new().select_random_item(&mut rng).is_err() );
+
+    let empty = Mixture::from(wlist![(1, el()), (0, wl())]);
+    assert!( empty.select_random_value(&mut rng).is_err() );
+
+    let single = Mixture::from(wlist![(0, wll()), (1, wlist![(1, str!("sup"))])]);
+    for _ in 0..TRIALS {
+        assert_eq!( single.select_random_value(&mut rng).unwrap(), "sup" );
+    }
+}
+
+#[test] fn select_float_rounding()
+{
+    // the greatest index overshoots the second component after subtracting the first
+    let mixture = Mixture::from(wlist![
+        (0.3, wlist![(1.0, "sup")]),
+        (0.7, wlist![(1.0, "nova")]),
+        (0.0, wlist![(1.0, "shard")]),
+    ]);
+
+    assert_eq!( mixture.select_random_value(&mut MaxRng).unwrap(), &"nova" );
+}
@@ -18,6 +18,8 @@ mod wlist {
 
     mod test_conversions;
 
+    mod test_operators;
+
     mod test_slice;
 
     mod test_traits;
@@ -28,6 +30,25 @@ mod wlist {
 
     mod test_querying;
 
+    mod test_ranking;
+
+    mod test_transforms;
+
+    mod test_grouping;
+
+    mod test_products;
+
+    mod test_dice;
+
+    mod test_inference;
+
+    mod test_composition;
+
+    mod test_text;
+
+    #[cfg(feature = "csv")]
+    mod test_csv;
+
     mod test_wlist_mut;
 
     mod test_random;
@@ -36,10 +57,61 @@ mod wlist {
 }
 
 
+mod wmap
+{
+    mod test_map;
+
+    mod test_random;
+}
+
+
+mod mixture
+{
+    mod test_mixture;
+}
+
+
+mod markov
+{
+    mod test_markov;
+}
+
+
+mod grammar
+{
+    mod test_grammar;
+}
+
+
+#[cfg(feature = "mmap")]
+mod mapped
+{
+    mod test_mapped;
+}
+
+
+#[cfg(feature = "registry")]
+mod registry
+{
+    mod test_registry;
+}
+
+
+#[cfg(feature = "loot-config")]
+mod loot
+{
+    mod test_loot_config;
+}
+
+
 #[cfg(feature = "frozen")]
 mod fwlist
 {
     mod test_constructors;
 
+    mod test_accessors;
+
     mod test_index;
+
+    mod test_text;
 }
@@ -0,0 +1,202 @@
+use weighted_list::*;
+
+use std::fs;
+use std::path::{ PathBuf };
+use std::sync::{ Arc };
+use std::time::{ Duration, Instant };
+
+
+/// Get an empty directory in the temporary directory which is unique to this test.
+fn temp_dir(name: &str) -> PathBuf
+{
+    let dir = std::env::temp_dir().join(format!("weighted-list-test-{}-{name}", std::process::id()));
+
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    dir
+}
+
+
+#[test] fn load()
+{
+    let dir = temp_dir("registry-load");
+    fs::write(dir.join("chest.txt"), "2  sup\n3  nova\n5  shard\n").unwrap();
+    fs::write(dir.join("barrel.txt"), "1  stick\n").unwrap();
+    fs::write(dir.join("notes.md"), "not a table").unwrap();
+    fs::create_dir(dir.join("nested.txt")).unwrap();
+
+    let registry = TableRegistry::<String, u32>::new(&dir);
+    assert_eq!( registry.dir(), dir );
+    assert!( registry.names().is_empty() );
+
+    assert!( registry.poll().is_empty() );
+    assert_eq!( registry.names(), vec!["barrel", "chest"] );
+
+    let chest = registry.get("chest").unwrap();
+    assert_eq!( chest.len(), 10 );
+    assert_eq!( chest.values().collect::<Vec<_>>(), vec!["sup", "nova", "shard"] );
+
+    assert!( registry.get("notes").is_none() );
+    assert!( registry.get("nested").is_none() );
+}
+
+#[test] fn reload()
+{
+    let dir = temp_dir("registry-reload");
+    let path = dir.join("chest.txt");
+    fs::write(&path, "2  sup\n").unwrap();
+
+    let registry = TableRegistry::<String, u32>::new(&dir);
+    registry.poll();
+    let old = registry.get("chest").unwrap();
+
+    // unchanged files are not reloaded
+    registry.poll();
+    assert!( Arc::ptr_eq(&old, &registry.get("chest").unwrap()) );
+
+    fs::write(&path, "2  sup\n3  nova\n").unwrap();
+    assert!( registry.poll().is_empty() );
+
+    let new = registry.get("chest").unwrap();
+    assert_eq!( new.len(), 5 );
+    assert_eq!( old.len(), 2 );
+
+    // rewrites of the same size are reloaded, even with the same modification time
+    let modified = fs::metadata(&path).unwrap().modified().unwrap();
+    fs::write(&path, "4  sup\n3  nova\n").unwrap();
+    fs::File::options().write(true).open(&path).unwrap()
+        .set_modified(modified).unwrap();
+
+    registry.poll();
+    assert_eq!( registry.get("chest").unwrap().len(), 7 );
+
+    // touching a file without changing it does not reload it
+    let old = registry.get("chest").unwrap();
+    fs::File::options().write(true).open(&path).unwrap()
+        .set_modified(std::time::SystemTime::now() + Duration::from_secs(10)).unwrap();
+
+    registry.poll();
+    assert!( Arc::ptr_eq(&old, &registry.get("chest").unwrap()) );
+}
+
+#[test] fn reload_settled()
+{
+    let dir = temp_dir("registry-reload-settled");
+    let path = dir.join("chest.txt");
+    fs::write(&path, "2  sup\n").unwrap();
+
+    let modified = std::time::SystemTime::now() - Duration::from_secs(60);
+    fs::File::options().write(true).open(&path).unwrap()
+        .set_modified(modified).unwrap();
+
+    let registry = TableRegistry::<String, u32>::new(&dir);
+    registry.poll();
+    let old = registry.get("chest").unwrap();
+
+    // files modified well before they were read are not read again while their modification time and size are unchanged
+    fs::write(&path, "4  sup\n").unwrap();
+    fs::File::options().write(true).open(&path).unwrap()
+        .set_modified(modified).unwrap();
+
+    registry.poll();
+    assert!( Arc::ptr_eq(&old, &registry.get("chest").unwrap()) );
+
+    fs::write(&path, "40  sup\n").unwrap();
+    registry.poll();
+    assert_eq!( registry.get("chest").unwrap().len(), 40 );
+}
+
+#[test] fn failed_parse()
+{
+    let dir = temp_dir("registry-failed-parse");
+    let path = dir.join("chest.txt");
+    fs::write(&path, "2  sup\n").unwrap();
+
+    let registry = TableRegistry::<String, u32>::new(&dir);
+    registry.poll();
+
+    fs::write(&path, "2  sup\n-3  nova\n").unwrap();
+    let errors = registry.poll();
+
+    assert_eq!( errors.len(), 1 );
+    assert!( errors[0].to_string().starts_with(&format!("Failed to load table from {}: ", path.display())) );
+
+    // the last good version is kept, and the error is only reported once
+    assert_eq!( registry.get("chest").unwrap().len(), 2 );
+    assert!( registry.poll().is_empty() );
+
+    fs::write(&path, "2  sup\n3  nova\n").unwrap();
+    assert!( registry.poll().is_empty() );
+    assert_eq!( registry.get("chest").unwrap().len(), 5 );
+}
+
+#[test] #[cfg(unix)] fn failed_read()
+{
+    let dir = temp_dir("registry-failed-read");
+    let path = dir.join("chest.txt");
+    fs::write(&path, "2  sup\n").unwrap();
+
+    let registry = TableRegistry::<String, u32>::new(&dir);
+    registry.poll();
+
+    // a dangling symlink is listed, but cannot be read
+    fs::remove_file(&path).unwrap();
+    std::os::unix::fs::symlink(dir.join("missing.txt"), &path).unwrap();
+
+    let errors = registry.poll();
+    assert_eq!( errors.len(), 1 );
+    assert!( errors[0].to_string().starts_with(&format!("Failed to load table from {}: ", path.display())) );
+    assert_eq!( registry.get("chest").unwrap().len(), 2 );
+}
+
+#[test] fn remove()
+{
+    let dir = temp_dir("registry-remove");
+    fs::write(dir.join("chest.txt"), "2  sup\n").unwrap();
+
+    let registry = TableRegistry::<String, u32>::new(&dir);
+    registry.poll();
+    let old = registry.get("chest").unwrap();
+
+    fs::remove_file(dir.join("chest.txt")).unwrap();
+    registry.poll();
+
+    assert!( registry.get("chest").is_none() );
+    assert_eq!( old.len(), 2 );
+}
+
+#[test] fn missing_dir()
+{
+    let dir = temp_dir("registry-missing").join("missing");
+    let registry = TableRegistry::<String, u32>::new(&dir);
+
+    let errors = registry.poll();
+    assert_eq!( errors.len(), 1 );
+    assert!( errors[0].to_string().starts_with(&format!("Failed to load table from {}: ", dir.display())) );
+}
+
+#[test] fn watch()
+{
+    let dir = temp_dir("registry-watch");
+    fs::write(dir.join("chest.txt"), "2  sup\n").unwrap();
+
+    let registry = Arc::new(TableRegistry::<String, u32>::new(&dir));
+    let handle = registry.watch(Duration::from_millis(5), |err| panic!("{err}"));
+
+    let wait_for = |len: u32| {
+        let start = Instant::now();
+        while registry.get("chest").map(|table| table.len()) != Some(len) {
+            assert!( start.elapsed() < Duration::from_secs(5), "table was not reloaded" );
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    };
+
+    wait_for(2);
+    fs::write(dir.join("chest.txt"), "2  sup\n3  nova\n").unwrap();
+    wait_for(5);
+
+    // the thread stops once the registry is dropped
+    drop(registry);
+    handle.join().unwrap();
+}
@@ -0,0 +1,95 @@
+use weighted_list::*;
+
+use ::serde::{ Serialize, Deserialize };
+use schemars::{ JsonSchema, schema_for };
+use serde_json::{ json };
+
+
+#[test] fn list()
+{
+    let schema = schema_for!(WList<String, i32>);
+
+    assert_eq!( schema.get("title"), Some(&json!("WeightedList_for_string_and_int32")) );
+    assert_eq!( schema.get("type"), Some(&json!("array")) );
+    assert_eq!(
+        schema.get("items"),
+        Some(&json!({
+            "type": "array",
+            "prefixItems": [
+                { "type": "integer", "format": "int32", "minimum": 0 },
+                { "type": "string" },
+            ],
+            "minItems": 2,
+            "maxItems": 2,
+        }))
+    );
+}
+
+#[test] fn item()
+{
+    let schema = schema_for!(WeightedItem<String, f64>);
+
+    assert_eq!( schema.get("type"), Some(&json!("array")) );
+    assert_eq!(
+        schema.get("prefixItems"),
+        Some(&json!([
+            { "type": "number", "format": "double", "minimum": 0 },
+            { "type": "string" },
+        ]))
+    );
+}
+
+#[test] #[cfg(feature = "frozen")] fn frozen()
+{
+    let schema = schema_for!(FWList<String, u32>);
+
+    assert_eq!( schema.get("title"), Some(&json!("FrozenWeightedList_for_string_and_uint32")) );
+    assert_eq!( schema.get("items"), schema_for!(WList<String, u32>).get("items") );
+}
+
+#[test] fn representations()
+{
+    #[allow(dead_code)]
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    struct Tables
+    {
+        pairs: WList<String, i32>,
+
+        #[serde(with = "weighted_list::repr::map")]
+        #[schemars(schema_with = "weighted_list::repr::map::json_schema::<String, i32>")]
+        map: WList<String, i32>,
+
+        #[serde(with = "weighted_list::repr::items")]
+        #[schemars(schema_with = "weighted_list::repr::items::json_schema::<String, i32>")]
+        items: WList<String, i32>,
+    }
+
+    let schema = schema_for!(Tables);
+    let properties = schema.get("properties").unwrap();
+    let weight = json!({ "type": "integer", "format": "int32", "minimum": 0 });
+
+    assert_eq!( properties["pairs"], json!({ "$ref": "#/$defs/WeightedList_for_string_and_int32" }) );
+
+    assert_eq!(
+        properties["map"],
+        json!({
+            "type": "object",
+            "additionalProperties": weight,
+        })
+    );
+
+    assert_eq!(
+        properties["items"],
+        json!({
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "weight": weight,
+                    "value": { "type": "string" },
+                },
+                "required": ["weight", "value"],
+            },
+        })
+    );
+}
@@ -0,0 +1,137 @@
+use crate::*;
+use weighted_list::*;
+
+use ::serde::{ Serialize, Deserialize };
+use serde_json::json;
+
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Tables
+{
+    #[serde(with = "weighted_list::repr::map")]
+    map: WList<String, u32>,
+
+    #[serde(with = "weighted_list::repr::items")]
+    items: WList<String, u32>,
+
+    #[serde(with = "weighted_list::repr::pairs")]
+    pairs: WList<String, u32>,
+}
+
+
+#[test] fn list_default()
+{
+    let json = serde_json::to_value(wl()).unwrap();
+    assert_eq!( json, json!([[2, "sup"], [3, "nova"], [5, "shard"]]) );
+
+    let list: WList<String, u32> = serde_json::from_value(json).unwrap();
+    assert_eq!( list, wl() );
+
+    assert_eq!( serde_json::to_string(&el()).unwrap(), "[]" );
+    assert!( serde_json::from_str::<WList<String, u32>>("[[2]]").is_err() );
+}
+
+#[test] fn item_default()
+{
+    let item = wit!(2, str!("sup"));
+    let json = serde_json::to_value(&item).unwrap();
+
+    assert_eq!( json, json!([2, "sup"]) );
+    assert_eq!( serde_json::from_value::<WItem<String, u32>>(json).unwrap(), item );
+}
+
+#[test] fn representations()
+{
+    let tables = Tables { map: wl(), items: wl(), pairs: wl() };
+    let json = serde_json::to_value(&tables).unwrap();
+
+    assert_eq!(
+        json,
+        json!({
+            "map": {"sup": 2, "nova": 3, "shard": 5},
+            "items": [
+                {"weight": 2, "value": "sup"},
+                {"weight": 3, "value": "nova"},
+                {"weight": 5, "value": "shard"},
+            ],
+            "pairs": [[2, "sup"], [3, "nova"], [5, "shard"]],
+        })
+    );
+
+    // the map preserves order, rather than sorting keys
+    let text = serde_json::to_string(&tables).unwrap();
+    assert!( text.contains(r#"{"sup":2,"nova":3,"shard":5}"#) );
+
+    assert_eq!( serde_json::from_str::<Tables>(&text).unwrap(), tables );
+}
+
+#[test] fn map_default()
+{
+    let json = json!([[2, "sup"], [3, "nova"], [5, "sup"]]);
+    let map: WMap<String, u32> = serde_json::from_value(json).unwrap();
+
+    assert_eq!( map.weight_of(&str!("sup")), Some(7) );
+    assert_eq!( serde_json::to_value(&map).unwrap(), json!([[7, "sup"], [3, "nova"]]) );
+}
+
+#[test] fn mixture()
+{
+    let mixture = Mixture::from(wlist![(3, wl()), (1, el())]);
+    let json = serde_json::to_value(&mixture).unwrap();
+
+    assert_eq!( json, json!([[3, [[2, "sup"], [3, "nova"], [5, "shard"]]], [1, []]]) );
+    assert_eq!( serde_json::from_value::<Mixture<String, u32>>(json).unwrap(), mixture );
+}
+
+#[test] fn markov_chain()
+{
+    let mut chain = MarkovChain::<char, u32>::new(1);
+    chain.train("abab".chars());
+
+    let json = serde_json::to_value(&chain).unwrap();
+    assert_eq!(
+        json,
+        json!({ "order": 1, "transitions": [[["a"], [[2, "b"]]], [["b"], [[1, "a"]]]] })
+    );
+
+    let back: MarkovChain<char, u32> = serde_json::from_value(json).unwrap();
+    assert_eq!( back.transitions(&['a']), Some(&wlist![(2, 'b')]) );
+
+    let bad = json!({ "order": 2, "transitions": [[["a"], [[1, "b"]]]] });
+    assert!( serde_json::from_value::<MarkovChain<char, u32>>(bad).is_err() );
+}
+
+#[test] fn grammar()
+{
+    let json = json!({ "rules": { "origin": [[1, "#noun.capitalise#"]], "noun": [[2, "wolf"]] } });
+    let grammar: Grammar<u32> = serde_json::from_value(json).unwrap();
+
+    assert_eq!( grammar.max_depth(), 32 );
+    assert_eq!( grammar.expand(&mut rand::rng(), "origin").unwrap(), "Wolf" );
+
+    assert_eq!(
+        serde_json::to_value(&grammar).unwrap(),
+        json!({ "rules": { "noun": [[2, "wolf"]], "origin": [[1, "#noun.capitalise#"]] }, "max_depth": 32 })
+    );
+
+    let bad = json!({ "rules": { "origin": [[1, "#unclosed"]] } });
+    assert!( serde_json::from_value::<Grammar<u32>>(bad).is_err() );
+}
+
+#[test] #[cfg(feature = "frozen")] fn frozen()
+{
+    let json = serde_json::to_value(fwl()).unwrap();
+    assert_eq!( json, json!([[2, "sup"], [3, "nova"], [5, "shard"]]) );
+
+    let list: FWList<String, u32> = serde_json::from_value(json).unwrap();
+    assert_eq!( list, fwl() );
+    assert_eq!( list.len(), 10 );
+
+    let mut json = serde_json::Deserializer::from_str(r#"{"sup": 2, "nova": 3}"#);
+    let list: FWList<String, u32> = weighted_list::repr::map::deserialize(&mut json).unwrap();
+    assert_eq!( list.items().map(|item| item.cumulative_weight()).collect::<Vec<_>>(), vec![2, 5] );
+
+    assert!( serde_json::from_value::<FWList<String, i32>>(json!([[2, "sup"], [-1, "nova"]])).is_err() );
+    assert!( serde_json::from_value::<FWList<String, f64>>(json!([[2.0, "sup"], [null, "nova"]])).is_err() );
+    assert!( serde_json::from_value::<FWList<String, u8>>(json!([[200, "sup"], [100, "nova"]])).is_err() );
+}
@@ -2,7 +2,7 @@
 
 use crate::str;
 
-use weighted_list::WList;
+use weighted_list::{ WList, WMap };
 
 #[cfg(feature = "frozen")]
 use weighted_list::FWList;
@@ -45,6 +45,11 @@ pub fn wll() -> WList<String, u32>
     WList::from(data_string(true))
 }
 
+pub fn wm() -> WMap<String, u32>
+{
+    WMap::from_iter(data_string(false))
+}
+
 
 /// Construct an empty `FrozenWeightedList` for testing.
 #[cfg(feature = "frozen")]
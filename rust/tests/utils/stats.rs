@@ -23,7 +23,7 @@ pub enum Method {
 pub fn test_binomial<V>(
     wlist: &WList<V, u32>,
     method: Method,
-) -> ()
+)
     where V: Clone + Eq + std::fmt::Display,
 {
     let mut rng = rand::rng();
@@ -91,6 +91,6 @@ pub fn test_binomial<V>(
     };
 
     for item in wlist.iter() {
-        test_binomial_single(&item);
+        test_binomial_single(item);
     }
 }
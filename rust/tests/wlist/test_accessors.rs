@@ -40,6 +40,45 @@ use weighted_list::*;
     );
 }
 
+#[test] fn collect_raw()
+{
+    assert_eq!( el().collect_raw(), vec![] );
+
+    assert_eq!(
+        wl().collect_raw(),
+        vec![
+            (2, &str!("sup")),
+            (3, &str!("nova")),
+            (5, &str!("shard")),
+        ]
+    );
+}
+
+#[test] fn into_raw()
+{
+    assert_eq!( el().into_raw(), vec![] );
+    assert_eq!( wl().into_raw(), data_string(false) );
+    assert_eq!( WList::from(wl().into_raw()), wl() );
+}
+
+#[test] fn unique_values()
+{
+    assert_eq!( el().unique_values().count(), 0 );
+    assert!( wl().unique_values().eq(["sup", "nova", "shard"]) );
+
+    let list = wlist![(2, "sup"), (3, "nova"), (5, "sup"), (7, "shard"), (1, "nova")];
+    assert!( list.unique_values().eq(&["sup", "nova", "shard"]) );
+}
+
+#[test] fn unique_values_hashed()
+{
+    assert_eq!( el().unique_values_hashed().count(), 0 );
+    assert!( wl().unique_values_hashed().eq(["sup", "nova", "shard"]) );
+
+    let list = wlist![(2, "sup"), (3, "nova"), (5, "sup"), (7, "shard"), (1, "nova")];
+    assert!( list.unique_values_hashed().eq(&["sup", "nova", "shard"]) );
+}
+
 #[test] fn expanded()
 {
     assert!(
@@ -0,0 +1,44 @@
+use crate::*;
+use weighted_list::*;
+
+
+#[test] fn flat_map()
+{
+    assert!( el().flat_map(|_| wl()).unwrap().is_empty() );
+
+    let regions = wlist![(3, "forest"), (1, "desert"), (2, "void")];
+    let mobs = regions.flat_map(|region| match *region {
+        "forest" => wlist![(1, "wolf"), (2, "spider")],
+        "desert" => wlist![(5, "husk")],
+        _        => WList::new(),
+    }).unwrap();
+
+    assert_eq!( mobs, wlist![(1.0, "wolf"), (2.0, "spider"), (1.0, "husk")] );
+    assert_eq!( mobs.len(), 4.0 );
+
+    let same = wl().flat_map(|v| wlist![(7, v.len())]).unwrap();
+    assert_eq!( same, wlist![(2.0, 3), (3.0, 4), (5.0, 5)] );
+}
+
+#[test] fn flatten()
+{
+    let nested = wlist![
+        (2, wl()),
+        (0, wll()),
+        (8, wlist![(1, str!("sup"))]),
+    ];
+
+    let flat = nested.flatten().unwrap();
+
+    assert_eq!( flat.total_items(), 10 );
+    assert_eq!( flat.len(), 10.0 );
+    assert_eq!( flat.iter().next().unwrap(), &wit!(0.4, str!("sup")) );
+    assert_eq!( flat.iter().last().unwrap(), &wit!(8.0, str!("sup")) );
+
+    let mut merged = flat.clone();
+    merged.merge_duplicates().prune();
+    assert_eq!(
+        merged,
+        wlist![(8.4, str!("sup")), (0.6, str!("nova")), (1.0, str!("shard"))]
+    );
+}
@@ -1,5 +1,7 @@
-// use crate::*;
-// use weighted_list::*;
+use std::collections::{ BTreeMap, HashMap };
+
+use crate::*;
+use weighted_list::*;
 
 
 // #[test] fn from_frozen_weighted_list()
@@ -7,3 +9,104 @@
 //     assert_eq!( WeightedList::from(fwl()), wl() );
 //     assert_eq!( WeightedList::from_iter(fwl()), wl() );
 // }
+
+#[test] fn from_hashmap()
+{
+    assert_eq!( WL::from(HashMap::new()), el() );
+
+    let mut list = WL::from(HashMap::from([
+        (str!("sup"), 2),
+        (str!("nova"), 3),
+        (str!("shard"), 5),
+    ]));
+    list.sort();
+
+    assert_eq!( list, wl() );
+}
+
+#[test] fn from_btreemap()
+{
+    assert_eq!( WL::from(BTreeMap::new()), el() );
+
+    assert_eq!(
+        WL::from(BTreeMap::from([
+            (str!("sup"), 2),
+            (str!("nova"), 3),
+            (str!("shard"), 5),
+        ])),
+        wlist![
+            (3, str!("nova")),
+            (5, str!("shard")),
+            (2, str!("sup")),
+        ]
+    );
+}
+
+#[cfg(feature = "indexmap")]
+#[test] fn from_indexmap()
+{
+    use indexmap::IndexMap;
+
+    assert_eq!( WL::from(IndexMap::new()), el() );
+
+    assert_eq!(
+        WL::from(IndexMap::from([
+            (str!("sup"), 2),
+            (str!("nova"), 3),
+            (str!("shard"), 5),
+        ])),
+        wl()
+    );
+
+    assert_eq!( WL::from(wl().into_index_map()), wl() );
+    assert_eq!(
+        wlist![(2, "sup"), (3, "nova"), (5, "sup")].into_index_map(),
+        IndexMap::from([("sup", 7), ("nova", 3)])
+    );
+}
+
+#[test] fn into_map()
+{
+    assert_eq!( el().into_map(), HashMap::new() );
+
+    assert_eq!(
+        wl().into_map(),
+        HashMap::from([
+            (str!("sup"), 2),
+            (str!("nova"), 3),
+            (str!("shard"), 5),
+        ])
+    );
+
+    assert_eq!(
+        wlist![(2, "sup"), (3, "nova"), (5, "sup")].into_map(),
+        HashMap::from([("sup", 7), ("nova", 3)])
+    );
+}
+
+#[test] fn from_parallel()
+{
+    assert_eq!( WL::from_parallel([], []).unwrap(), el() );
+
+    assert_eq!(
+        WL::from_parallel(
+            [str!("sup"), str!("nova"), str!("shard")],
+            [2, 3, 5]
+        ).unwrap(),
+        wl()
+    );
+
+    assert!( WL::from_parallel([str!("sup")], [2, 3]).is_err() );
+    assert!( WL::from_parallel([str!("sup"), str!("nova")], [2]).is_err() );
+}
+
+#[test] fn unzip()
+{
+    assert_eq!( el().unzip(), (vec![], vec![]) );
+
+    let (weights, values) = wl().unzip();
+    assert_eq!( weights, vec![2, 3, 5] );
+    assert_eq!( values, vec![str!("sup"), str!("nova"), str!("shard")] );
+
+    assert_eq!( WL::from_parallel(values, weights).unwrap(), wl() );
+}
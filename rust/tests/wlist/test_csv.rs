@@ -0,0 +1,116 @@
+use crate::*;
+use weighted_list::*;
+
+
+const SHEET: &str = "\
+id,value,notes,weight
+1,sup,first,2
+2,nova,,3
+3,shard,\"rare, shiny\",5
+";
+
+
+fn read(data: &str) -> Result<WList<String, u32>, Box<dyn std::error::Error>>
+{
+    WList::from_csv_reader()
+        .reader(data.as_bytes())
+        .value_parser(|value| value.parse::<String>())
+        .call()
+}
+
+fn write(list: &WList<String, u32>, probability: bool, cumulative: bool) -> String
+{
+    let mut out = Vec::new();
+
+    list.to_csv_writer()
+        .writer(&mut out)
+        .probability(probability)
+        .cumulative(cumulative)
+        .call()
+        .unwrap();
+
+    String::from_utf8(out).unwrap()
+}
+
+
+#[test] fn from_csv_reader()
+{
+    assert_eq!( read(SHEET).unwrap(), wl() );
+    assert_eq!( read("weight,value\n").unwrap(), el() );
+
+    let list = WList::<u32, f64>::from_csv_reader()
+        .reader("1\t2.5\t10\n2\t0.5\t20\n".as_bytes())
+        .has_headers(false)
+        .delimiter(b'\t')
+        .weight_column(1)
+        .value_column(2)
+        .value_parser(|value| value.parse::<u32>())
+        .call()
+        .unwrap();
+    assert_eq!( list, wlist![(2.5, 10), (0.5, 20)] );
+
+    let list = WList::<String, u32>::from_csv_reader()
+        .reader(SHEET.as_bytes())
+        .value_column("notes")
+        .value_parser(|value| Ok::<_, String>(value.to_uppercase()))
+        .call()
+        .unwrap();
+    assert_eq!( list.values().collect::<Vec<_>>(), ["FIRST", "", "RARE, SHINY"] );
+}
+
+#[test] fn from_csv_reader_errors()
+{
+    assert_eq!(
+        read("weight,name\n2,sup").unwrap_err().to_string(),
+        "Invalid CSV at line 1, column \"value\": Column not found in headers"
+    );
+    assert_eq!(
+        read("weight,value\n2,sup\nx,nova").unwrap_err().to_string(),
+        "Invalid CSV at line 3, column \"weight\": Invalid weight \"x\""
+    );
+
+    let err = WList::<u32, u32>::from_csv_reader()
+        .reader("2,1\n3".as_bytes())
+        .has_headers(false)
+        .value_parser(|value| value.parse::<u32>())
+        .call()
+        .unwrap_err();
+    assert!( err.to_string().contains("found record with 1 field") );
+
+    let err = WList::<u32, u32>::from_csv_reader()
+        .reader("2,1\n3,-1".as_bytes())
+        .has_headers(false)
+        .value_parser(|value| value.parse::<u32>())
+        .call()
+        .unwrap_err();
+    assert_eq!( err.to_string(), "Invalid CSV at line 2, column 1: Invalid value \"-1\": invalid digit found in string" );
+
+    let err = WList::<u32, u32>::from_csv_reader()
+        .reader("2,1".as_bytes())
+        .has_headers(false)
+        .weight_column("weight")
+        .value_parser(|value| value.parse::<u32>())
+        .call()
+        .unwrap_err();
+    assert!( err.to_string().contains("only be found by name") );
+}
+
+#[test] fn to_csv_writer()
+{
+    assert_eq!( write(&wl(), false, false), "weight,value\n2,sup\n3,nova\n5,shard\n" );
+    assert_eq!( write(&wl(), false, true), "weight,value,cumulative\n2,sup,2\n3,nova,5\n5,shard,10\n" );
+    assert_eq!( write(&el(), true, true), "weight,value,probability,cumulative\n" );
+
+    let list = wlist![(1, str!("rare, shiny")), (0, str!("zero"))];
+    assert_eq!( write(&list, true, false), "weight,value,probability\n1,\"rare, shiny\",1\n0,zero,0\n" );
+
+    let mut out = Vec::new();
+    wl().to_csv_writer().writer(&mut out).has_headers(false).delimiter(b'\t').call().unwrap();
+    assert_eq!( String::from_utf8(out).unwrap(), "2\tsup\n3\tnova\n5\tshard\n" );
+}
+
+#[test] fn round_trip()
+{
+    let list = wlist![(2, str!("sup")), (3, str!("rare, \"shiny\"")), (5, str!(""))];
+    assert_eq!( read(&write(&list, true, true)).unwrap(), list );
+}
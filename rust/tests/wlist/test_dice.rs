@@ -0,0 +1,140 @@
+use weighted_list::*;
+
+
+type Dist = WList<i64, u64>;
+
+fn weight_of(dist: &Dist, value: i64) -> Option<u64>
+{
+    dist.iter().find(|item| item.value == value).map(|item| item.weight)
+}
+
+
+#[test] fn convolve()
+{
+    let d6 = Dist::from_expanded(1..=6);
+    let empty = Dist::new();
+
+    assert!( d6.convolve(&empty).unwrap().is_empty() );
+    assert_eq!( d6.convolve(&wlist![(1, 0)]).unwrap(), d6 );
+
+    let sums = d6.convolve(&d6).unwrap();
+    assert_eq!( sums.total_items(), 11 );
+    assert_eq!( sums.len(), 36 );
+    assert_eq!( weight_of(&sums, 7), Some(6) );
+
+    let huge = wlist![(u64::MAX, 0_i64)];
+    assert!( huge.convolve(&wlist![(2, 0)]).is_err() );
+}
+
+#[test] fn from_dice_sums()
+{
+    assert_eq!( Dist::from_dice("5").unwrap(), wlist![(1, 5)] );
+    assert_eq!( Dist::from_dice("d6").unwrap(), Dist::from_expanded(1..=6) );
+    assert_eq!( Dist::from_dice("1d6-1").unwrap(), Dist::from_expanded(0..=5) );
+    assert_eq!( Dist::from_dice("d%").unwrap(), Dist::from_expanded(1..=100) );
+
+    let d6 = Dist::from_expanded(1..=6);
+    let expected = d6.convolve(&d6).unwrap()
+        .convolve(&d6).unwrap()
+        .convolve(&wlist![(1, 2)]).unwrap();
+
+    let dist = Dist::from_dice("3d6+2").unwrap();
+    assert_eq!( dist, expected );
+    assert_eq!( dist.len(), 216 );
+    assert_eq!( dist.values().copied().collect::<Vec<_>>(), (5..=20).collect::<Vec<_>>() );
+
+    let dist = Dist::from_dice("1d4 - 1d4").unwrap();
+    assert_eq!( dist, wlist![(1, -3), (2, -2), (3, -1), (4, 0), (3, 1), (2, 2), (1, 3)] );
+}
+
+#[test] fn from_dice_keep()
+{
+    let dist = Dist::from_dice("2d20 keep highest").unwrap();
+    assert_eq!( dist.len(), 400 );
+    assert_eq!( weight_of(&dist, 1), Some(1) );
+    assert_eq!( weight_of(&dist, 20), Some(39) );
+
+    assert_eq!(
+        Dist::from_dice("2d20kl").unwrap(),
+        dist.mapped_values(|v| 21 - v).into_iter().rev().collect()
+    );
+
+    assert_eq!( Dist::from_dice("3d6k3").unwrap(), Dist::from_dice("3d6").unwrap() );
+
+    // 4d6 drop lowest, a classic for ability scores
+    let dist = Dist::from_dice("4d6kh3").unwrap();
+    assert_eq!( dist.len(), 1296 );
+    assert_eq!( weight_of(&dist, 3), Some(1) );
+    assert_eq!( weight_of(&dist, 18), Some(21) );
+}
+
+#[test] fn from_dice_keep_many()
+{
+    let dist = WList::<i64, u128>::from_dice("20d20kh10").unwrap();
+    assert_eq!( dist.len(), 20_u128.pow(20) );
+    assert_eq!( dist.values().copied().collect::<Vec<_>>(), (10..=200).collect::<Vec<_>>() );
+
+    // the top 10 are all 20s when at least 10 dice roll a 20
+    let mut binomial = 1;
+    let mut max_ways = 0;
+    for twenties in 0..=20 {
+        if twenties >= 10 {
+            max_ways += binomial * 19_u128.pow(20 - twenties);
+        }
+        binomial = binomial * (20 - twenties as u128) / (twenties as u128 + 1);
+    }
+    assert_eq!( dist.iter().last().map(|item| item.weight), Some(max_ways) );
+
+    let dist = WList::<i64, u128>::from_dice("20d20kl10").unwrap();
+    assert_eq!( dist.iter().next().map(|item| item.weight), Some(max_ways) );
+
+    assert!( Dist::from_dice("30d20kh10").is_err() );
+}
+
+#[test] fn from_dice_explode()
+{
+    assert_eq!(
+        Dist::from_dice_with_depth("d4!", 0).unwrap(),
+        Dist::from_dice("d4").unwrap()
+    );
+
+    assert_eq!(
+        Dist::from_dice_with_depth("d4!", 2).unwrap(),
+        wlist![
+            (16, 1), (16, 2), (16, 3),
+            (4, 5), (4, 6), (4, 7),
+            (1, 9), (1, 10), (1, 11), (1, 12),
+        ]
+    );
+
+    let dist = Dist::from_dice("d6!").unwrap();
+    assert_eq!( dist.len(), 6_u64.pow(4) );
+    assert_eq!( dist.values().max(), Some(&24) );
+}
+
+#[test] fn from_dice_errors()
+{
+    assert!( Dist::from_dice("").is_err() );
+    assert!( Dist::from_dice("3d").is_err() );
+    assert!( Dist::from_dice("2d6kh3").is_err() );
+    assert!( Dist::from_dice("3d6 * 2").is_err() );
+    assert!( Dist::from_dice("d6 keep").is_err() );
+
+    assert!( WList::<i64, u8>::from_dice("3d20").is_err() );
+    assert!( WList::<i64, u8>::from_dice("2d10").is_ok() );
+}
+
+#[test] fn from_dice_limits()
+{
+    assert!( Dist::from_dice("1000000d1000").is_err() );
+    assert!( Dist::from_dice("d4294967295").is_err() );
+    assert!( Dist::from_dice("101d6").is_err() );
+    assert!( Dist::from_dice("d1001").is_err() );
+    assert!( Dist::from_dice("d1000").is_ok() );
+
+    let err = Dist::from_dice_with_depth("d1000!", 10).unwrap_err();
+    assert!( err.is::<errors::DiceParseError>() );
+
+    let dist = WList::<i64, f64>::from_dice_with_depth("d1000!", 9).unwrap();
+    assert_eq!( dist.values().max(), Some(&10_000) );
+}
@@ -0,0 +1,60 @@
+use crate::*;
+use weighted_list::*;
+
+
+fn joint() -> WList<(&'static str, &'static str), u32>
+{
+    wlist![
+        (2, ("forest", "wolf")),
+        (3, ("desert", "husk")),
+        (5, ("forest", "spider")),
+        (7, ("ocean", "drowned")),
+        (13, ("desert", "spider")),
+    ]
+}
+
+
+#[test] fn group_by_key()
+{
+    assert_eq!( el().group_by_key(|v| v.len()), WList::<usize, u32>::new() );
+
+    assert_eq!(
+        joint().group_by_key(|(biome, _)| *biome),
+        wlist![(7, "forest"), (16, "desert"), (7, "ocean")]
+    );
+    assert_eq!(
+        joint().group_by_key(|(_, mob)| *mob),
+        wlist![(2, "wolf"), (3, "husk"), (18, "spider"), (7, "drowned")]
+    );
+
+    assert_eq!( wl().group_by_key(|_| ()), wlist![(10, ())] );
+    assert_eq!( wl().group_by_key(|v| v.clone()), wl() );
+}
+
+#[test] fn partition_by()
+{
+    assert!( el().partition_by(|v| v.len()).is_empty() );
+
+    let parts = joint().partition_by(|(biome, _)| *biome);
+    assert_eq!( parts.len(), 3 );
+    assert_eq!( parts["forest"], wlist![(2, ("forest", "wolf")), (5, ("forest", "spider"))] );
+    assert_eq!( parts["desert"], wlist![(3, ("desert", "husk")), (13, ("desert", "spider"))] );
+    assert_eq!( parts["ocean"], wlist![(7, ("ocean", "drowned"))] );
+}
+
+#[test] fn conditional_on()
+{
+    let list = joint();
+
+    let forest = list.conditional_on(&"forest", |(biome, _)| *biome).unwrap();
+    assert_eq!(
+        forest,
+        wlist![(2.0 / 7.0, ("forest", "wolf")), (5.0 / 7.0, ("forest", "spider"))]
+    );
+
+    let ocean = list.conditional_on(&"ocean", |(biome, _)| *biome).unwrap();
+    assert_eq!( ocean, wlist![(1.0, ("ocean", "drowned"))] );
+
+    let tundra = list.conditional_on(&"tundra", |(biome, _)| *biome).unwrap();
+    assert!( tundra.is_empty() );
+}
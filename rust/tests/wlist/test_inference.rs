@@ -0,0 +1,112 @@
+use weighted_list::*;
+
+
+fn approx(left: f64, right: f64) -> bool
+{
+    (left - right).abs() < 1e-9
+}
+
+fn coins() -> WList<&'static str, f64>
+{
+    wlist![(0.5, "fair"), (0.5, "biased")]
+}
+
+fn heads(coin: &&str) -> f64
+{
+    if *coin == "fair" { 0.5 } else { 0.9 }
+}
+
+fn likelihood(coin: &&str, is_heads: &bool) -> f64
+{
+    if *is_heads { heads(coin) } else { 1.0 - heads(coin) }
+}
+
+
+#[test] fn bayes_update()
+{
+    let mut list = coins();
+    let evidence = list.bayes_update(heads).unwrap();
+
+    assert!( approx(evidence, 0.7) );
+    assert!( approx(list.len(), 1.0) );
+    assert!( approx(list[0.0].weight, 0.25 / 0.7) );
+
+    // unnormalised prior gives the same posterior
+    let mut list = wlist![(2.0, "fair"), (2.0, "biased")];
+    let evidence = list.bayes_update(heads).unwrap();
+
+    assert!( approx(evidence, 0.7) );
+    assert!( approx(list[0.0].weight, 0.25 / 0.7) );
+}
+
+#[test] fn bayes_update_zero()
+{
+    let mut list = coins();
+    assert!( list.bayes_update(|_| 0.0).is_err() );
+    assert_eq!( list, coins() );
+
+    let mut list = wlist![(0.0, "fair"), (1.0, "biased")];
+    assert!( list.bayes_update(|coin| if *coin == "fair" { 1.0 } else { 0.0 }).is_err() );
+
+    let mut list = WList::<&str, f64>::new();
+    assert!( list.bayes_update(heads).is_err() );
+}
+
+#[test] fn bayes_update_negative()
+{
+    let mut list = coins();
+    assert!( list.bayes_update(|coin| if *coin == "fair" { -0.1 } else { 0.9 }).is_err() );
+    assert!( list.bayes_update(|_| f64::NAN).is_err() );
+    assert_eq!( list, coins() );
+
+    let mut list = wlist![(-0.5, "fair"), (1.0, "biased")];
+    assert!( list.bayes_update(heads).is_err() );
+}
+
+#[test] fn sequential_update()
+{
+    let observations = [true, true, false];
+
+    let mut stepwise = coins();
+    let mut evidence = 1.0;
+    for observation in &observations {
+        evidence *= stepwise.bayes_update(|coin| likelihood(coin, observation)).unwrap();
+    }
+
+    let mut list = coins();
+    let log_evidence = list.sequential_update(observations, likelihood).unwrap();
+
+    assert!( approx(log_evidence, evidence.ln()) );
+    for (left, right) in list.weights().zip(stepwise.weights()) {
+        assert!( approx(left, right) );
+    }
+}
+
+#[test] fn sequential_update_underflow()
+{
+    let mut list = coins();
+    let observations = [true; 3000];
+
+    let log_evidence = list.sequential_update(observations, likelihood).unwrap();
+
+    assert!( log_evidence.is_finite() );
+    assert!( list.weights().all(f64::is_finite) );
+    assert!( approx(list.len(), 1.0) );
+    assert!( list[0.0].value == "biased" );
+
+    let mut list = coins();
+    assert!( list.sequential_update([true], |_, _| 0.0).is_err() );
+    assert_eq!( list, coins() );
+}
+
+#[test] fn sequential_update_negative()
+{
+    let mut list = coins();
+    assert!( list.sequential_update([true], |coin, _| if *coin == "fair" { -0.1 } else { 0.9 }).is_err() );
+    assert!( list.sequential_update([true], |_, _| f64::NAN).is_err() );
+    assert_eq!( list, coins() );
+
+    let mut list = wlist![(-0.5, "fair"), (1.0, "biased")];
+    assert!( list.sequential_update([true], likelihood).is_err() );
+    assert_eq!( list, wlist![(-0.5, "fair"), (1.0, "biased")] );
+}
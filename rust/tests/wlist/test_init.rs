@@ -133,6 +133,6 @@ use weighted_list::*;
     
     // FromIterator Vec<WItem<V,W>>
     let list = wl();
-    let iter = list.items().into_iter().map(|item| item.clone());
+    let iter = list.items().into_iter().cloned();
     assert_eq!( iter.collect::<WL>(), wl() );
 }
@@ -0,0 +1,142 @@
+use crate::*;
+use weighted_list::*;
+
+
+#[test] fn add()
+{
+    assert_eq!( el() + el(), el() );
+    assert_eq!( el() + wl(), wl() );
+    assert_eq!( wl() + el(), wl() );
+
+    assert_eq!(
+        wl() + wlist![(2, str!("sup"))],
+        wlist![
+            (2, str!("sup")),
+            (3, str!("nova")),
+            (5, str!("shard")),
+            (2, str!("sup")),
+        ]
+    );
+
+    let mut list = wl();
+    list += wlist![(7, str!("cortex"))];
+    assert_eq!( list, wll().weighted_slice(..17) );
+}
+
+#[test] fn bitor()
+{
+    assert_eq!( el() | el(), el() );
+    assert_eq!( el() | wl(), wl() );
+
+    assert_eq!(
+        wl() | wl(),
+        wlist![(4, str!("sup")), (6, str!("nova")), (10, str!("shard"))]
+    );
+
+    let mut list = wl();
+    list |= wlist![(1, str!("nova")), (7, str!("cortex"))];
+    assert_eq!(
+        list,
+        wlist![
+            (2, str!("sup")),
+            (4, str!("nova")),
+            (5, str!("shard")),
+            (7, str!("cortex")),
+        ]
+    );
+}
+
+#[test] fn bitand()
+{
+    assert_eq!( el() & wl(), el() );
+    assert_eq!( wl() & el(), el() );
+    assert_eq!( wl() & wl(), wl() );
+
+    assert_eq!(
+        wl() & wlist![(7, str!("shard")), (1, str!("sup")), (7, str!("cortex"))],
+        wlist![(1, str!("sup")), (5, str!("shard"))]
+    );
+
+    let mut list = wlist![(2, "sup"), (3, "nova"), (5, "sup")];
+    list &= wlist![(1, "sup"), (3, "sup")];
+    assert_eq!( list, wlist![(2, "sup"), (2, "sup")] );
+}
+
+#[test] fn sub()
+{
+    assert_eq!( el() - wl(), el() );
+    assert_eq!( wl() - el(), wl() );
+    assert_eq!( wl() - wl(), el() );
+
+    assert_eq!(
+        wl() - wlist![(1, str!("sup")), (9, str!("shard")), (7, str!("cortex"))],
+        wlist![(1, str!("sup")), (3, str!("nova"))]
+    );
+
+    let mut list = wlist![(2, "sup"), (3, "nova"), (5, "sup")];
+    list -= wlist![(3, "sup")];
+    assert_eq!( list, wlist![(3, "nova"), (4, "sup")] );
+}
+
+#[test] fn mul_div()
+{
+    assert_eq!( el() * 2, el() );
+    assert_eq!(
+        wl() * 2,
+        wlist![(4, str!("sup")), (6, str!("nova")), (10, str!("shard"))]
+    );
+    assert_eq!( wl() * 2 / 2, wl() );
+
+    let mut list = wlist![(1.0, "sup"), (3.0, "nova")];
+    list /= 2.0;
+    assert_eq!( list, wlist![(0.5, "sup"), (1.5, "nova")] );
+    list *= 4.0;
+    assert_eq!( list, wlist![(2.0, "sup"), (6.0, "nova")] );
+}
+
+#[test] fn mul_overflow()
+{
+    let list = wlist![(2_u8, "sup"), (100, "nova")];
+
+    assert_eq!( list.clone().checked_mul(2).unwrap(), list.clone() * 2 );
+    assert!( list.clone().checked_mul(3).is_err() );
+
+    let result = std::panic::catch_unwind(|| wlist![(100_u8, "nova")] * 3);
+    assert!( result.is_err() );
+}
+
+#[test] fn div_truncates()
+{
+    let mut list = wl() / 3;
+    assert_eq!(
+        list,
+        wlist![(0, str!("sup")), (1, str!("nova")), (1, str!("shard"))]
+    );
+    assert_eq!( list.len(), 2 );
+
+    list /= 2;
+    assert_eq!( list.len(), 0 );
+    assert_eq!( list.total_items(), 3 );
+    assert!( list.is_zero() );
+}
+
+#[test] fn extend_pairs()
+{
+    let mut list = el();
+    list.extend(data_string(false));
+    assert_eq!( list, wl() );
+
+    list.extend([(7, str!("cortex"))]);
+    assert_eq!( list.total_items(), 4 );
+}
+
+#[test] fn sum()
+{
+    assert_eq!( Vec::<WL>::new().into_iter().sum::<WL>(), el() );
+    assert_eq!( [wl()].into_iter().sum::<WL>(), wl() );
+
+    assert_eq!(
+        [wl(), el(), wl()].into_iter().sum::<WL>(),
+        wl() + wl()
+    );
+}
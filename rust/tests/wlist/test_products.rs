@@ -0,0 +1,82 @@
+use crate::*;
+use weighted_list::*;
+
+
+#[test] fn product()
+{
+    assert!( el().product(&wl()).unwrap().is_empty() );
+    assert!( wl().product(&el()).unwrap().is_empty() );
+
+    let elements = wlist![(1, "fire"), (4, "ice")];
+    assert_eq!(
+        wl().product(&elements).unwrap(),
+        wlist![
+            (2, (str!("sup"), "fire")),
+            (8, (str!("sup"), "ice")),
+            (3, (str!("nova"), "fire")),
+            (12, (str!("nova"), "ice")),
+            (5, (str!("shard"), "fire")),
+            (20, (str!("shard"), "ice")),
+        ]
+    );
+
+    let product = wl().product(&wll()).unwrap();
+    assert_eq!( product.total_items(), 18 );
+    assert_eq!( product.len(), wl().len() * wll().len() );
+}
+
+#[test] fn product_overflow()
+{
+    let list = wlist![(16_u8, 'a'), (1, 'b')];
+
+    assert!( list.product(&list).is_err() );
+    assert!( wlist![(15_u8, 'a')].product(&list).is_ok() );
+}
+
+#[test] fn product_large_unsigned()
+{
+    let big = wlist![(u128::MAX / 2, 'a')];
+    let two = wlist![(2_u128, 'b')];
+
+    assert_eq!( big.product(&two).unwrap(), wlist![(u128::MAX - 1, ('a', 'b'))] );
+    assert_eq!( big.zip_with(&two, |_, _| ()).unwrap(), wlist![(u128::MAX - 1, ())] );
+    assert!( big.product(&big).is_err() );
+}
+
+#[test] fn product_all()
+{
+    assert_eq!(
+        WList::<&str, u32>::product_all([]).unwrap(),
+        wlist![(1, vec![])]
+    );
+
+    let coin = wlist![(1, 'H'), (2, 'T')];
+    let flips = WList::product_all([&coin, &coin, &coin]).unwrap();
+
+    assert_eq!( flips.total_items(), 8 );
+    assert_eq!( flips.len(), 27 );
+    assert_eq!( flips[0].value, vec!['H', 'H', 'H'] );
+    assert_eq!( flips.iter().last().unwrap(), &wit!(8, vec!['T', 'T', 'T']) );
+
+    let list = wlist![(16_u8, 'a')];
+    assert!( WList::product_all([&list, &list]).is_err() );
+}
+
+#[test] fn zip_with()
+{
+    let d4 = WList::<u32, u32>::from_expanded(1..=4);
+    let sums = d4.zip_with(&d4, |a, b| a + b).unwrap();
+
+    assert_eq!(
+        sums,
+        wlist![(1, 2), (2, 3), (3, 4), (4, 5), (3, 6), (2, 7), (1, 8)]
+    );
+
+    assert_eq!(
+        wl().zip_with(&wl(), |a, b| a.len() == b.len()).unwrap(),
+        wlist![(38, true), (62, false)]
+    );
+
+    let list = wlist![(8_u8, 0), (8, 0)];
+    assert!( list.zip_with(&list, |a, b| a + b).is_err() );
+}
@@ -6,6 +6,8 @@ use weighted_list::*;
 {
     assert_eq!( el().len(), 0 );
     assert_eq!( wl().len(), 10 );
+
+    assert!( WList::<String, f64>::new().len().is_sign_positive() );
 }
 
 #[test] fn with_capacity()
@@ -1,6 +1,7 @@
 use itertools::*;
 
 use crate::*;
+use weighted_list::*;
 
 
 #[test] fn contains_weight()
@@ -18,13 +19,13 @@ use crate::*;
 #[test] fn weighted_sum()
 {
     assert_eq!(
-        0 as u32,
-        el().weighted_sum(|v| v.chars().collect_vec().len() as u32)
+        0,
+        el().weighted_sum::<u32>(|v| v.chars().collect_vec().len() as u32)
     );
 
     assert_eq!(
-        2*3 + 3*4 + 5*5 as u32,
-        wl().weighted_sum(|v| v.chars().collect_vec().len() as u32)
+        2*3 + 3*4 + 5*5,
+        wl().weighted_sum::<u32>(|v| v.chars().collect_vec().len() as u32)
     );
 }
 
@@ -41,3 +42,81 @@ use crate::*;
         wl().normalised_weighted_sum(|v| v.chars().collect_vec().len() as f64).unwrap()
     );
 }
+
+#[test] fn count_with_weight()
+{
+    assert_eq!( el().count_with_weight(2), 0 );
+    assert_eq!( wl().count_with_weight(2), 1 );
+    assert_eq!( wl().count_with_weight(4), 0 );
+    assert_eq!( wlist![(2, "sup"), (2, "nova"), (5, "shard")].count_with_weight(2), 2 );
+}
+
+#[test] fn position_of()
+{
+    assert_eq!( el().position_of(&str!("sup")), None );
+    assert_eq!( wl().position_of(&str!("sup")), Some(0) );
+    assert_eq!( wl().position_of(&str!("shard")), Some(2) );
+    assert_eq!( wl().position_of(&str!("qi")), None );
+}
+
+#[test] fn nth_position_of()
+{
+    let list = wlist![(2, "sup"), (3, "nova"), (5, "sup"), (7, "sup")];
+
+    assert_eq!( list.nth_position_of(&"sup", 0), Some(0) );
+    assert_eq!( list.nth_position_of(&"sup", 1), Some(2) );
+    assert_eq!( list.nth_position_of(&"sup", 2), Some(3) );
+    assert_eq!( list.nth_position_of(&"sup", 3), None );
+    assert_eq!( list.nth_position_of(&"nova", 1), None );
+}
+
+#[test] fn weighted_position_of()
+{
+    assert_eq!( el().weighted_position_of(&str!("sup")), None );
+    assert_eq!( wl().weighted_position_of(&str!("sup")), Some(0) );
+    assert_eq!( wl().weighted_position_of(&str!("nova")), Some(2) );
+    assert_eq!( wl().weighted_position_of(&str!("shard")), Some(5) );
+    assert_eq!( wl().weighted_position_of(&str!("qi")), None );
+
+    let list = wl();
+    let idx = list.weighted_position_of(&str!("shard")).unwrap();
+    assert_eq!( list[idx].value, "shard" );
+}
+
+#[test] fn find_where()
+{
+    assert_eq!( el().find_where(|_| true), None );
+    assert_eq!( wl().find_where(|item| item.value.len() == 4), Some(&wit!(3, str!("nova"))) );
+    assert_eq!( wl().find_where(|item| item.weight > 10), None );
+}
+
+#[test] fn find_all_where()
+{
+    assert_eq!( el().find_all_where(|_| true).count(), 0 );
+
+    let list = wl();
+
+    assert!(
+        list.find_all_where(|_| true).eq([
+            (0..2,  &wit!(2, str!("sup"))),
+            (2..5,  &wit!(3, str!("nova"))),
+            (5..10, &wit!(5, str!("shard"))),
+        ])
+    );
+    assert!(
+        list.find_all_where(|item| item.weight != 3).eq([
+            (0..2,  &wit!(2, str!("sup"))),
+            (5..10, &wit!(5, str!("shard"))),
+        ])
+    );
+}
+
+#[test] fn entropy()
+{
+    assert_eq!( el().entropy().unwrap(), 0.0 );
+    assert_eq!( wlist![(5, str!("sup"))].entropy().unwrap(), 0.0 );
+    assert_eq!( wlist![(0, str!("sup")), (3, str!("nova")), (3, str!("shard"))].entropy().unwrap(), 1.0 );
+
+    let expected = -(0.2 * 0.2_f64.log2() + 0.3 * 0.3_f64.log2() + 0.5 * 0.5_f64.log2());
+    assert!( (wl().entropy().unwrap() - expected).abs() < 1e-12 );
+}
@@ -18,7 +18,7 @@ const TRIALS: usize = 50;
         (5, str!("woah"))
     ];
 
-    let outs = vec!["sup", "woah"];
+    let outs = ["sup", "woah"];
     let mut out;
 
     for _ in 0..TRIALS {
@@ -26,7 +26,7 @@ const TRIALS: usize = 50;
         assert!( outs.contains(&out.unwrap().as_str()) );
     }
 
-    let outs = vec![wit!(100, str!("sup")), wit!(5, str!("woah"))];
+    let outs = [wit!(100, str!("sup")), wit!(5, str!("woah"))];
     let mut out;
 
     for _ in 0..TRIALS {
@@ -35,20 +35,35 @@ const TRIALS: usize = 50;
     }
 }
 
+#[test] fn select_fractional_weights()
+{
+    let list = wlist![(0.2, "sup"), (0.3, "nova"), (0.5, "shard")];
+    let mut rng = rand::rng();
+
+    let mut counts = std::collections::HashMap::new();
+    for _ in 0..3000 {
+        *counts.entry(*list.select_random_value(&mut rng).unwrap()).or_insert(0) += 1;
+    }
+
+    assert!( (450..750).contains(&counts["sup"]) );
+    assert!( (750..1050).contains(&counts["nova"]) );
+    assert!( (1350..1650).contains(&counts["shard"]) );
+}
+
 #[test] fn take_single()
 {
     let mut rng = rand::rng();
 
     let mut list = wl();
-    list.take_entire_random(&mut rng); assert_eq!( list.total_items(), 2 );
-    list.take_entire_random(&mut rng); assert_eq!( list.total_items(), 1 );
-    list.take_entire_random(&mut rng); assert_eq!( list.total_items(), 0 );
+    let _ = list.take_entire_random(&mut rng); assert_eq!( list.total_items(), 2 );
+    let _ = list.take_entire_random(&mut rng); assert_eq!( list.total_items(), 1 );
+    let _ = list.take_entire_random(&mut rng); assert_eq!( list.total_items(), 0 );
 
     let mut list = wl();
-    list.take_by_random(&mut rng, 1); assert_eq!( list.total_items(), 3 );
-    list.take_by_random(&mut rng, 5); assert_eq!( list.total_items(), 2 );
-    list.take_by_random(&mut rng, 5); assert_eq!( list.total_items(), 1 );
-    list.take_by_random(&mut rng, 5); assert_eq!( list.total_items(), 0 );
+    let _ = list.take_by_random(&mut rng, 1); assert_eq!( list.total_items(), 3 );
+    let _ = list.take_by_random(&mut rng, 5); assert_eq!( list.total_items(), 2 );
+    let _ = list.take_by_random(&mut rng, 5); assert_eq!( list.total_items(), 1 );
+    let _ = list.take_by_random(&mut rng, 5); assert_eq!( list.total_items(), 0 );
 }
 
 #[test] fn select_many()
@@ -58,7 +73,7 @@ const TRIALS: usize = 50;
     let list = wl();
     let count = list.len() as usize;
 
-    let valid = vec!["sup", "nova", "shard"];
+    let valid = ["sup", "nova", "shard"];
     let mut results;
 
     '_standard: {
@@ -181,7 +196,7 @@ const TRIALS: usize = 50;
 {
     let mut rng = rand::rng();
 
-    let valid = vec!["sup", "nova", "shard"];
+    let valid = ["sup", "nova", "shard"];
     let mut results;
 
     '_standard: {
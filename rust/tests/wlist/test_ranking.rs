@@ -0,0 +1,86 @@
+use crate::*;
+use weighted_list::*;
+
+
+#[test] fn count_from()
+{
+    assert_eq!( WL::count_from([]), el() );
+
+    assert_eq!(
+        WList::count_from(["qi", "sup", "sup"]),
+        wlist![(1, "qi"), (2, "sup")]
+    );
+
+    assert_eq!(
+        WList::count_from([
+            str!("sup"), str!("nova"), str!("shard"),
+            str!("nova"), str!("shard"), str!("sup"),
+            str!("shard"), str!("nova"), str!("shard"), str!("shard"),
+        ]),
+        wl()
+    );
+
+    let values = ["sup", "nova", "nova", "shard", "sup"];
+    assert_eq!(
+        WList::<_, u32>::count_from(values),
+        WList::from_expanded(values)
+    );
+}
+
+#[test] fn most_common()
+{
+    assert!( el().most_common(2).is_empty() );
+    assert!( wl().most_common(0).is_empty() );
+
+    assert_eq!(
+        wl().most_common(2),
+        vec![&wit!(5, str!("shard")), &wit!(3, str!("nova"))]
+    );
+    assert_eq!( wl().most_common(10).len(), 3 );
+
+    let list = wlist![(2, "sup"), (3, "nova"), (3, "cortex")];
+    assert_eq!( list.most_common(1), vec![&wit!(3, "nova")] );
+}
+
+#[test] fn least_common()
+{
+    assert!( el().least_common(2).is_empty() );
+
+    assert_eq!(
+        wl().least_common(2),
+        vec![&wit!(2, str!("sup")), &wit!(3, str!("nova"))]
+    );
+
+    let list = wlist![(3.0, "sup"), (1.5, "nova"), (1.5, "cortex")];
+    assert_eq!( list.least_common(1), vec![&wit!(1.5, "nova")] );
+}
+
+#[test] fn top_k_by_weight()
+{
+    assert_eq!( el().top_k_by_weight(2), el() );
+    assert_eq!( wl().top_k_by_weight(3), wl() );
+
+    assert_eq!(
+        wll().top_k_by_weight(3),
+        wlist![(7, str!("cortex")), (13, str!("origin")), (20, str!("vision"))]
+    );
+
+    let list = wlist![(5, "shard"), (2, "sup"), (7, "cortex")];
+    assert_eq!( list.top_k_by_weight(2), wlist![(5, "shard"), (7, "cortex")] );
+}
+
+#[test] fn pop_max_min_weight()
+{
+    assert_eq!( el().pop_max_weight(), None );
+    assert_eq!( el().pop_min_weight(), None );
+
+    let mut list = wll();
+
+    assert_eq!( list.pop_max_weight(), Some(wit!(20, str!("vision"))) );
+    assert_eq!( list.pop_min_weight(), Some(wit!(2, str!("sup"))) );
+    assert_eq!( list.pop_max_weight(), Some(wit!(13, str!("origin"))) );
+    assert_eq!( list.total_items(), 3 );
+
+    let mut list = wlist![(2, "sup"), (2, "nova")];
+    assert_eq!( list.pop_max_weight(), Some(wit!(2, "sup")) );
+}
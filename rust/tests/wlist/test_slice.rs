@@ -1,5 +1,5 @@
 use crate::*;
-// use weighted_list::*;
+use weighted_list::*;
 
 
 #[test] fn sort()
@@ -12,3 +12,96 @@ use crate::*;
     list.sort();
     assert_eq!( list, wl() );
 }
+
+#[test] #[allow(clippy::reversed_empty_ranges)] fn weighted_slice()
+{
+    assert_eq!( el().weighted_slice(..), el() );
+    assert_eq!( el().weighted_slice(2..7), el() );
+
+    assert_eq!( wl().weighted_slice(..), wl() );
+    assert_eq!( wl().weighted_slice(0..10), wl() );
+    assert_eq!( wl().weighted_slice(0..100), wl() );
+
+    assert_eq!(
+        wl().weighted_slice(2..7),
+        wlist![(3, str!("nova")), (2, str!("shard"))]
+    );
+    assert_eq!(
+        wl().weighted_slice(1..6),
+        wlist![(1, str!("sup")), (3, str!("nova")), (1, str!("shard"))]
+    );
+    assert_eq!(
+        wl().weighted_slice(3..4),
+        wlist![(1, str!("nova"))]
+    );
+
+    assert_eq!( wl().weighted_slice(4..4), el() );
+    assert_eq!( wl().weighted_slice(7..4), el() );
+    assert_eq!( wl().weighted_slice(10..), el() );
+}
+
+#[test] fn weighted_slice_bounds()
+{
+    assert_eq!( wl().weighted_slice(..2), wlist![(2, str!("sup"))] );
+    assert_eq!( wl().weighted_slice(..=2), wlist![(2, str!("sup")), (1, str!("nova"))] );
+    assert_eq!( wl().weighted_slice(2..=4), wlist![(3, str!("nova"))] );
+    assert_eq!( wl().weighted_slice(6..), wlist![(4, str!("shard"))] );
+}
+
+#[test] fn weighted_slice_float()
+{
+    let list = wlist![(0.5, "qi"), (1.5, "sup"), (2.0, "nova")];
+
+    assert_eq!(
+        list.weighted_slice(0.25..2.5),
+        wlist![(0.25, "qi"), (1.5, "sup"), (0.5, "nova")]
+    );
+}
+
+#[test] fn weighted_slice_float_bounds()
+{
+    use std::ops::Bound;
+
+    let list = wlist![(0.5, "qi"), (1.5, "sup"), (2.0, "nova")];
+
+    assert_eq!(
+        list.weighted_slice(..=2.5),
+        list.weighted_slice(..2.5)
+    );
+    assert_eq!(
+        list.weighted_slice((Bound::Excluded(0.5), Bound::Unbounded)),
+        wlist![(1.5, "sup"), (2.0, "nova")]
+    );
+}
+
+#[test] fn weighted_slice_extreme_bounds()
+{
+    use std::ops::Bound;
+
+    let list = wl();
+
+    assert_eq!( list.weighted_slice(..=u32::MAX), list );
+    assert_eq!( list.weighted_slice((Bound::Excluded(u32::MAX), Bound::Unbounded)), wlist![] );
+    assert_eq!(
+        list.weighted_slice((Bound::Excluded(1), Bound::Included(u32::MAX))),
+        wlist![(3, str!("nova")), (5, str!("shard"))]
+    );
+}
+
+#[test] fn weighted_slice_zero_weights()
+{
+    let list = wlist![(2, "sup"), (0, "qi"), (3, "nova")];
+
+    assert_eq!( list.weighted_slice(..), wlist![(2, "sup"), (3, "nova")] );
+}
+
+#[test] fn weighted_slice_ref()
+{
+    let list = wl();
+
+    assert_eq!(
+        list.weighted_slice_ref(1..3),
+        wlist![(1, &list[0].value), (1, &list[2].value)]
+    );
+    assert_eq!( list.weighted_slice_ref(..).len(), list.len() );
+}
@@ -0,0 +1,86 @@
+use crate::*;
+use weighted_list::*;
+
+
+#[test] fn display()
+{
+    assert_eq!( el().to_string(), "" );
+    assert_eq!( wl().to_string(), "2  sup\n3  nova\n5  shard\n" );
+
+    let list = wlist![(2.5, str!("multi word")), (10.0, str!(" padded ")), (1.0, str!(""))];
+    assert_eq!( list.to_string(), "2.5  multi word\n10   \" padded \"\n1    \"\"\n" );
+}
+
+#[test] fn parse()
+{
+    let list: WList<String, u32> = "2 sup\n3 nova\n5 shard".parse().unwrap();
+    assert_eq!( list, wl() );
+
+    let list: WList<String, u32> = "
+        // comment
+
+        2   |   sup    // trailing comment
+        3|nova
+        5 | \"shard\"  // quoted
+    ".parse().unwrap();
+    assert_eq!( list, wl() );
+
+    let list: WList<String, f64> = r#"2.5 | "multi word value" "#.parse().unwrap();
+    assert_eq!( list, wlist![(2.5, str!("multi word value"))] );
+
+    let list: WList<String, u32> = r#"1 "say \"hi\"\n""#.parse().unwrap();
+    assert_eq!( list, wlist![(1, str!("say \"hi\"\n"))] );
+
+    let list: WList<String, u32> = "1 http://sup.nova".parse().unwrap();
+    assert_eq!( list, wlist![(1, str!("http://sup.nova"))] );
+
+    let list: WList<u8, u32> = "".parse().unwrap();
+    assert_eq!( list, WList::new() );
+}
+
+#[test] fn parse_percentages()
+{
+    let list: WList<String, u32> = "20% sup\n30% nova\n50%  shard".parse().unwrap();
+    assert_eq!( list, wlist![(20, str!("sup")), (30, str!("nova")), (50, str!("shard"))] );
+
+    let list: WList<String, f64> = "12.5% | sup".parse().unwrap();
+    assert_eq!( list, wlist![(12.5, str!("sup"))] );
+}
+
+#[test] fn parse_errors()
+{
+    fn error(source: &str) -> String
+    {
+        source.parse::<WList<u32, u32>>().unwrap_err().to_string()
+    }
+
+    assert_eq!( error("2 3\nx 4"), "Invalid weighted list at line 2, column 1: Invalid weight" );
+    assert_eq!( error("  2 x"), "Invalid weighted list at line 1, column 5: Invalid value" );
+    assert_eq!( error("2"), "Invalid weighted list at line 1, column 2: Expected a value after the weight" );
+    assert_eq!( error("2 | // 3"), "Invalid weighted list at line 1, column 5: Expected a value after the weight" );
+    assert_eq!( error("| 3"), "Invalid weighted list at line 1, column 1: Expected a weight before the value" );
+    assert_eq!( error("2%3"), "Invalid weighted list at line 1, column 3: Expected whitespace or `|` after the weight" );
+    assert_eq!( error("2% 3\n4 5"), "Invalid weighted list at line 2, column 1: Cannot mix percentages with plain weights" );
+    assert_eq!( error("2 \"3"), "Invalid weighted list at line 1, column 3: Unterminated quoted value" );
+    assert_eq!( error("2 \"3\\q\""), "Invalid weighted list at line 1, column 5: Invalid escape sequence" );
+    assert_eq!( error("2 \"3\" 4"), "Invalid weighted list at line 1, column 7: Unexpected text after quoted value" );
+}
+
+#[test] fn round_trip()
+{
+    assert_eq!( wl().to_string().parse::<WList<String, u32>>().unwrap(), wl() );
+    assert_eq!( el().to_string().parse::<WList<String, u32>>().unwrap(), el() );
+
+    let list: WList<String, f64> = wlist![
+        (0.1, str!("")),
+        (-2.0, str!("  spaced  ")),
+        (1e-7, str!("\"quoted\" // and \\ escaped")),
+        (300.0, str!("| piped\nmulti\r\nline\t")),
+        (2.5, str!("100%")),
+    ];
+    assert_eq!( list.to_string().parse::<WList<String, f64>>().unwrap(), list );
+
+    let list: WList<i64, u32> = wlist![(1, -3), (20, 0), (300, 12)];
+    assert_eq!( list.to_string(), "1    -3\n20   0\n300  12\n" );
+    assert_eq!( list.to_string().parse::<WList<i64, u32>>().unwrap(), list );
+}
@@ -0,0 +1,96 @@
+use crate::*;
+use weighted_list::*;
+
+
+#[test] fn mapped_values()
+{
+    assert_eq!( el().mapped_values(|v| v.len()), WList::<usize, u32>::new() );
+
+    assert_eq!(
+        wl().mapped_values(|v| v.len()),
+        wlist![(2, 3), (3, 4), (5, 5)]
+    );
+}
+
+#[test] fn map_values()
+{
+    let mut list = wl();
+    list.map_values(|v| v.to_uppercase());
+
+    assert_eq!(
+        list,
+        wlist![(2, str!("SUP")), (3, str!("NOVA")), (5, str!("SHARD"))]
+    );
+}
+
+#[test] fn try_mapped_values()
+{
+    let list = wlist![(2, "2"), (3, "3"), (5, "5")];
+    assert_eq!(
+        list.try_mapped_values(|v| v.parse::<u32>()),
+        Ok(wlist![(2, 2), (3, 3), (5, 5)])
+    );
+
+    let list = wlist![(2, "2"), (3, "x"), (5, "5"), (7, "")];
+    let errors = list.try_mapped_values(|v| v.parse::<u32>()).unwrap_err();
+    assert_eq!( errors.len(), 2 );
+}
+
+#[test] fn try_map_values()
+{
+    let mut list = wl();
+    assert!(
+        list.try_map_values(|v|
+            if v.len() > 3 { Ok(v.repeat(2)) } else { Err(v.clone()) }
+        ).is_err()
+    );
+    assert_eq!( list, wl() );
+
+    assert!( list.try_map_values(|v| Ok::<_, ()>(v.repeat(2))).is_ok() );
+    assert_eq!(
+        list,
+        wlist![(2, str!("supsup")), (3, str!("novanova")), (5, str!("shardshard"))]
+    );
+}
+
+#[test] fn filter_map_values()
+{
+    let list = wlist![(2, "2"), (3, "x"), (5, "5")];
+    assert_eq!(
+        list.filter_mapped_values(|v| v.parse::<u32>().ok()),
+        wlist![(2, 2), (5, 5)]
+    );
+
+    let mut list = wl();
+    list.filter_map_values(|v| (v != "nova").then(|| v.to_uppercase()));
+    assert_eq!( list, wlist![(2, str!("SUP")), (5, str!("SHARD"))] );
+}
+
+#[test] fn map_items()
+{
+    assert_eq!(
+        wl().mapped_items(|item| (item.weight + 1, item.value.len())),
+        wlist![(3, 3), (4, 4), (6, 5)]
+    );
+
+    let mut list = wl();
+    list.map_items(|item| (item.weight * 2, item.value.clone()));
+    assert_eq!( list, wl() * 2 );
+}
+
+#[test] fn replace_value()
+{
+    let mut list = wlist![(2, "sup"), (3, "nova"), (5, "sup")];
+
+    assert_eq!(
+        list.replaced_value(&"sup", "shard"),
+        wlist![(2, "shard"), (3, "nova"), (5, "shard")]
+    );
+    assert_eq!( list, wlist![(2, "sup"), (3, "nova"), (5, "sup")] );
+
+    list.replace_value(&"qi", "shard");
+    assert_eq!( list, wlist![(2, "sup"), (3, "nova"), (5, "sup")] );
+
+    list.replace_value(&"nova", "cortex");
+    assert_eq!( list, wlist![(2, "sup"), (3, "cortex"), (5, "sup")] );
+}
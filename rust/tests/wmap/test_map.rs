@@ -0,0 +1,124 @@
+use crate::*;
+use weighted_list::*;
+
+
+#[test] fn constructors()
+{
+    let _: WMap<String, u32> = WMap::new();
+    let _: WMap<String, u32> = WMap::default();
+
+    let map: WMap<String, u32> = WMap::with_capacity(42);
+    assert!( map.is_empty() );
+
+    assert_eq!( wm().into_list(), wl() );
+    assert_eq!( WMap::from_iter(data_string(true)).into_list(), wll() );
+}
+
+#[test] fn from_expanded()
+{
+    assert_eq!( WMap::<String, u32>::from_expanded([]), WMap::new() );
+
+    assert_eq!(
+        WMap::<_, u32>::from_expanded(["qi", "sup", "sup"]).into_list(),
+        wlist![(1, "qi"), (2, "sup")]
+    );
+}
+
+#[test] fn conversions()
+{
+    assert_eq!( WL::from(wm()), wl() );
+    assert_eq!( WMap::from(wl()), wm() );
+
+    assert_eq!(
+        WMap::from(wlist![(2, "sup"), (3, "nova"), (5, "sup")]).into_list(),
+        wlist![(7, "sup"), (3, "nova")]
+    );
+
+    assert_eq!( wm().as_list(), &wl() );
+    assert_eq!( wm().len(), 10 );
+    assert_eq!( wm().total_items(), 3 );
+}
+
+#[test] fn weight_of()
+{
+    let map = wm();
+
+    assert_eq!( map.weight_of(&str!("sup")), Some(2) );
+    assert_eq!( map.weight_of(&str!("shard")), Some(5) );
+    assert_eq!( map.weight_of(&str!("qi")), None );
+
+    assert!( map.contains_value(&str!("nova")) );
+    assert!( !map.contains_value(&str!("qi")) );
+
+    assert_eq!( map.position_of(&str!("nova")), Some(1) );
+}
+
+#[test] fn merge()
+{
+    let mut map = wm();
+
+    map.merge_value(str!("sup"))
+        .merge_new_item(2, str!("nova"))
+        .merge_item(wit!(7, str!("cortex")));
+
+    assert_eq!(
+        map.into_list(),
+        wlist![
+            (3, str!("sup")),
+            (5, str!("nova")),
+            (5, str!("shard")),
+            (7, str!("cortex")),
+        ]
+    );
+}
+
+#[test] fn set_weight()
+{
+    let mut map = wm();
+
+    assert_eq!( map.set_weight(str!("nova"), 7), Some(3) );
+    assert_eq!( map.set_weight(str!("qi"), 1), None );
+
+    assert_eq!(
+        map.into_list(),
+        wlist![
+            (2, str!("sup")),
+            (7, str!("nova")),
+            (5, str!("shard")),
+            (1, str!("qi")),
+        ]
+    );
+}
+
+#[test] fn remove_value()
+{
+    let mut map = wm();
+
+    assert_eq!( map.remove_value(&str!("qi")), None );
+    assert_eq!( map.remove_value(&str!("sup")), Some(wit!(2, str!("sup"))) );
+    assert_eq!( map.weight_of(&str!("shard")), Some(5) );
+    assert_eq!( map.position_of(&str!("shard")), Some(1) );
+    assert_eq!( map.clone().into_list(), wlist![(3, str!("nova")), (5, str!("shard"))] );
+
+    assert_eq!( map.remove_value(&str!("shard")), Some(wit!(5, str!("shard"))) );
+    assert_eq!( map.remove_value(&str!("nova")), Some(wit!(3, str!("nova"))) );
+    assert!( map.is_empty() );
+}
+
+#[test] fn prune()
+{
+    let mut map = wm();
+    map.set_weight(str!("sup"), 0);
+    map.prune();
+
+    assert_eq!( map.into_list(), wlist![(3, str!("nova")), (5, str!("shard"))] );
+}
+
+#[test] fn index()
+{
+    let map = wm();
+
+    assert_eq!( map[0].value, "sup" );
+    assert_eq!( map[4].value, "nova" );
+    assert_eq!( map[9].value, "shard" );
+}
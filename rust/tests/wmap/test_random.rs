@@ -0,0 +1,57 @@
+use crate::*;
+use weighted_list::*;
+
+
+const TRIALS: usize = 50;
+
+
+#[test] fn select()
+{
+    let mut rng = rand::rng();
+    let map = wm();
+
+    for _ in 0..TRIALS {
+        assert!( map.contains_value(map.select_random_value(&mut rng).unwrap()) );
+    }
+
+    assert!( WMap::<String, u32>::new().select_random_item(&mut rng).is_err() );
+
+    let results = map.select_random_values().rng(&mut rng).count(TRIALS).call();
+    assert_eq!( results.len(), TRIALS );
+
+    let results = map.select_random_values_unique().rng(&mut rng).count(TRIALS).call();
+    assert_eq!( results.len(), 3 );
+}
+
+#[test] fn take()
+{
+    let mut rng = rand::rng();
+
+    let mut map = wm();
+    for _ in 0..10 {
+        let _ = map.take_one_random(&mut rng);
+    }
+    assert!( map.is_empty() );
+    assert!( map.take_one_random(&mut rng).is_err() );
+
+    let mut map = wm();
+    let taken = map.take_entire_random(&mut rng).unwrap();
+    assert_eq!( map.total_items(), 2 );
+
+    // the remaining items keep their order
+    let expected = wm().into_list().iter()
+        .filter(|item| item.value != taken.value)
+        .cloned()
+        .collect::<WList<_,_>>();
+    assert_eq!( map.into_list(), expected );
+
+    let mut map = wm();
+    let results = map.take_random_values().rng(&mut rng).count(TRIALS).call();
+    assert_eq!( results.len(), 3 );
+    assert!( map.is_empty() );
+
+    let mut map = wm();
+    let results = map.take_random_values_unique().rng(&mut rng).count(2).decrement(2).call();
+    assert_eq!( results.len(), 2 );
+    assert_eq!( map.len(), 6 );
+}